@@ -0,0 +1,88 @@
+/* Generate one test per day and part from the answers manifest, checking both the committed
+ * inputs and the worked examples of the puzzle statements.
+ */
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use toml::Value;
+
+fn main() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+
+    let answers: Value = fs::read_to_string(&manifest)
+        .expect("cannot read answers manifest")
+        .parse()
+        .expect("invalid answers manifest");
+    let mut tests = String::new();
+
+    for (day, entry) in answers.as_table().expect("manifest must be a table") {
+        let solution = format!("{}::Day{}", day, day.trim_start_matches("day"));
+
+        /* answers for the committed input, if any */
+        let input = match entry.get("input") {
+            Some(path) => format!("&read_input({:?})", as_str(path)),
+            None => "\"\"".to_owned(),
+        };
+        write_parts(&mut tests, day, &solution, &input, entry);
+
+        /* answers for the worked examples */
+        let examples = entry.get("examples").and_then(Value::as_array);
+        for (idx, example) in examples.into_iter().flatten().enumerate() {
+            let name = format!("{}_example{}", day, idx + 1);
+            let input = format!("{:?}", as_str(&example["input"]));
+            write_parts(&mut tests, &name, &solution, &input, example);
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("answers.rs");
+    fs::write(out, tests).expect("cannot write generated tests");
+}
+
+fn write_parts(tests: &mut String, name: &str, solution: &str, input: &str, entry: &Value) {
+    for part in 1..=2 {
+        if let Some(answer) = entry.get(format!("part{}", part)) {
+            let answer = to_text(answer);
+            writeln!(tests, "#[test]\nfn {}_part{}() {{", name, part).unwrap();
+
+            /* puzzle parameters of the example, if any */
+            let params = entry.get("params").and_then(Value::as_table);
+            let binding = if params.is_some() { "let mut" } else { "let" };
+            writeln!(
+                tests,
+                "    {} solution: {} = Default::default();",
+                binding, solution
+            )
+            .unwrap();
+            for (param, value) in params.into_iter().flatten() {
+                writeln!(
+                    tests,
+                    "    solution.set_param({:?}, {:?}).unwrap();",
+                    param,
+                    to_text(value)
+                )
+                .unwrap();
+            }
+            writeln!(
+                tests,
+                "    check(&solution, {}, {}, {:?});\n}}\n",
+                input, part, answer
+            )
+            .unwrap();
+        }
+    }
+}
+
+/* Text of a value, without quotes for strings */
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn as_str(value: &Value) -> &str {
+    value.as_str().expect("input must be a string")
+}
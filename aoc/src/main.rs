@@ -0,0 +1,107 @@
+use std::env;
+use std::error::Error;
+use std::process;
+
+use common::{read_input, Result, Solution};
+
+const USAGE: &str = "usage: aoc run <day> [--part <1|2>] [--input <file>] [--<param> [<value>]]...
+
+puzzle parameters, true when given without a value:
+    day 3:  --engine <sweep|dense|map> --report <true|false>
+    day 4:  --check <true|false> --strategy1 <strategy> --strategy2 <strategy>
+            strategies: most-asleep most-frequent-minute most-consistent longest-nap most-nights
+    day 5:  --table <true|false> --rule <ascii|unicode> --pairs <file>
+    day 6:  --threshold <distance> --report <true|false> --engine <flood|naive>
+            --metric <manhattan|chebyshev|euclidean|weighted:<wx>,<wy>>
+    day 7:  --workers <nb> --base-cost <seconds>
+    day 9:  --multiplier <factor> --circle <arena|linked-list>
+            --replay <file.csv|file.json|file.txt> --max-snapshot <marbles>
+    day 10: --max-size <size>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let res = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        _ => Err(Box::<dyn Error>::from(USAGE)),
+    };
+    if let Err(e) = res {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+/* {{{ Run */
+
+struct RunArgs {
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+    /* puzzle parameters, by name */
+    params: Vec<(String, String)>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs> {
+    let mut iter = args.iter().peekable();
+    let day = match iter.next() {
+        Some(day) => day.parse()?,
+        None => return Err(Box::<dyn Error>::from(USAGE)),
+    };
+    let mut run_args = RunArgs {
+        day,
+        part: None,
+        input: None,
+        params: Vec::new(),
+    };
+
+    while let Some(arg) = iter.next() {
+        /* the next option is never taken as a value */
+        let value = iter.next_if(|value| !value.starts_with("--"));
+        let missing = || format!("missing value for {}", arg);
+
+        match arg.as_ref() {
+            "--part" => run_args.part = Some(value.ok_or_else(missing)?.parse()?),
+            "--input" => run_args.input = Some(value.ok_or_else(missing)?.clone()),
+            _ => match arg.get(2..) {
+                /* boolean parameters can be given as flags */
+                Some(name) if arg.starts_with("--") => run_args.params.push((
+                    name.to_owned(),
+                    value.map_or("true", String::as_str).to_owned(),
+                )),
+                _ => return Err(format!("unknown option {}\n{}", arg, USAGE).into()),
+            },
+        }
+    }
+    Ok(run_args)
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let args = parse_run_args(args)?;
+
+    let input = read_input(args.input.as_deref())?;
+
+    match args.day {
+        1 => run_day::<day1::Day1>(&args, &input),
+        2 => run_day::<day2::Day2>(&args, &input),
+        3 => run_day::<day3::Day3>(&args, &input),
+        4 => run_day::<day4::Day4>(&args, &input),
+        5 => run_day::<day5::Day5>(&args, &input),
+        6 => run_day::<day6::Day6>(&args, &input),
+        7 => run_day::<day7::Day7>(&args, &input),
+        8 => run_day::<day8::Day8>(&args, &input),
+        9 => run_day::<day9::Day9>(&args, &input),
+        10 => run_day::<day10::Day10>(&args, &input),
+        day => Err(format!("no solution for day {}", day).into()),
+    }
+}
+
+fn run_day<S: Solution + Default>(args: &RunArgs, input: &str) -> Result<()> {
+    let mut solution = S::default();
+
+    for (name, value) in &args.params {
+        solution.set_param(name, value)?;
+    }
+    common::run(&solution, input, args.part)
+}
+
+/* }}} */
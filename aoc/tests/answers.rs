@@ -0,0 +1,21 @@
+use std::fs;
+use std::path::Path;
+
+use common::Solution;
+
+fn read_input(path: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path);
+    fs::read_to_string(path).unwrap()
+}
+
+fn check<S: Solution>(solution: &S, input: &str, part: u8, expected: &str) {
+    let input = solution.parse(input).unwrap();
+    let answer = match part {
+        1 => solution.part1(&input).unwrap().to_string(),
+        _ => solution.part2(&input).unwrap().to_string(),
+    };
+
+    assert_eq!(answer, expected);
+}
+
+include!(concat!(env!("OUT_DIR"), "/answers.rs"));
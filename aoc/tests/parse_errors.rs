@@ -0,0 +1,106 @@
+use common::{ParseError, Solution};
+
+fn parse_error<S: Solution>(solution: &S, input: &str) -> ParseError {
+    match solution.parse(input) {
+        Ok(_) => panic!("input should not parse"),
+        Err(e) => *e.downcast::<ParseError>().unwrap(),
+    }
+}
+
+#[test]
+fn day3_invalid_claim() {
+    let err = parse_error(&day3::Day3::default(), "#1 @ 1,3: 4x4\n#2 @ 3,1: 4xfour\n");
+
+    assert_eq!(err.line, Some(2));
+    assert_eq!(err.text, "#2 @ 3,1: 4xfour");
+    assert_eq!(err.pattern, "#{d} @ {d},{d}: {d}x{d}");
+    assert_eq!(err.field, Some("height"));
+    assert_eq!(
+        err.to_string(),
+        "line 2: invalid height in \"#2 @ 3,1: 4xfour\", expected \"#{d} @ {d},{d}: {d}x{d}\""
+    );
+}
+
+#[test]
+fn day3_claim_too_large() {
+    let err = parse_error(&day3::Day3::default(), "#1 @ 4294967295,0: 2x2\n");
+
+    assert_eq!(err.line, Some(1));
+    assert_eq!(err.field, Some("width"));
+}
+
+#[test]
+fn day4_invalid_shift() {
+    let err = parse_error(
+        &day4::Day4::default(),
+        "[1518-11-01 00:00] Guard 10 begins shift\n",
+    );
+
+    assert_eq!(err.line, Some(1));
+    assert_eq!(err.pattern, "Guard #{d} begins shift");
+    assert_eq!(err.field, Some("guard id"));
+}
+
+#[test]
+fn day4_invalid_date() {
+    let err = parse_error(
+        &day4::Day4::default(),
+        "[1518-02-28 23:58] Guard #10 begins shift\n[1518-02-29 00:05] falls asleep\n",
+    );
+
+    assert_eq!(err.line, Some(2));
+    assert_eq!(err.text, "[1518-02-29 00:05] falls asleep");
+    assert_eq!(err.field, Some("date"));
+}
+
+#[test]
+fn day6_invalid_point() {
+    let err = parse_error(&day6::Day6::default(), "1, 1\n1, 6\n8 3\n");
+
+    assert_eq!(err.line, Some(3));
+    assert_eq!(err.field, Some("y"));
+}
+
+#[test]
+fn day7_invalid_dep() {
+    let err = parse_error(
+        &day7::Day7::default(),
+        "Step C must be done before step A can begin.\n",
+    );
+
+    assert_eq!(err.line, Some(1));
+    assert_eq!(err.field, Some("step"));
+}
+
+#[test]
+fn day7_step_not_a_letter() {
+    let day7 = day7::Day7::default();
+
+    let err = parse_error(&day7, "Step 1 must be finished before step 2 can begin.\n");
+    assert_eq!(err.field, Some("dependency"));
+
+    let err = parse_error(&day7, "Step A must be finished before step b can begin.\n");
+    assert_eq!(err.field, Some("step"));
+}
+
+#[test]
+fn day10_invalid_point() {
+    let err = parse_error(
+        &day10::Day10::default(),
+        "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  z>\n",
+    );
+
+    assert_eq!(err.line, Some(2));
+    assert_eq!(err.field, Some("y velocity"));
+}
+
+#[test]
+fn day9_invalid_game() {
+    let err = parse_error(
+        &day9::Day9::default(),
+        "9 players; last marble is worth 25 points\n0 players; last marble is worth 25 points\n",
+    );
+
+    assert_eq!(err.line, Some(2));
+    assert_eq!(err.field, Some("players"));
+}
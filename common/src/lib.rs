@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/* A day's solution: the input is parsed once, and both parts are computed from it.
+ *
+ * The solution itself holds the puzzle parameters, if any, so that the worked examples of the
+ * puzzle statements can be run with the same code.
+ */
+pub trait Solution {
+    /* day of the puzzle, used when printing answers */
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+
+    /* Set a puzzle parameter by name. Days without parameters reject all of them. */
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<()> {
+        Err(format!("unknown parameter {} for day {}", name, Self::DAY).into())
+    }
+}
+
+/* Parse the input, and print the answer of the requested part, or of both parts if None. */
+pub fn run<S: Solution>(solution: &S, input: &str, part: Option<u8>) -> Result<()> {
+    let input = solution.parse(input)?;
+
+    match part {
+        None => {
+            print_answer(S::DAY, 1, &solution.part1(&input)?);
+            print_answer(S::DAY, 2, &solution.part2(&input)?);
+        }
+        Some(1) => print_answer(S::DAY, 1, &solution.part1(&input)?),
+        Some(2) => print_answer(S::DAY, 2, &solution.part2(&input)?),
+        Some(part) => return Err(format!("invalid part {}", part).into()),
+    }
+    Ok(())
+}
+
+fn print_answer(day: u8, part: u8, answer: &dyn Display) {
+    let answer = answer.to_string();
+
+    /* multi-line answers are printed starting on their own line */
+    if answer.contains('\n') {
+        println!("day{}, part{}:\n{}", day, part, answer);
+    } else {
+        println!("day{}, part{}: {}", day, part, answer);
+    }
+}
+
+/* {{{ Command line */
+
+/* Parse the arguments of a binary taking options and at most one input file, and return the
+ * file. Options are handled by on_option, taking their values from the remaining arguments, and
+ * returning false for unknown options.
+ */
+pub fn parse_args<I, F>(args: I, mut on_option: F) -> Result<Option<String>>
+where
+    I: IntoIterator<Item = String>,
+    F: FnMut(&str, &mut dyn Iterator<Item = String>) -> Result<bool>,
+{
+    let mut path = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            if !on_option(&arg, &mut args)? {
+                return Err(format!("unknown option {}", arg).into());
+            }
+        } else if path.is_none() {
+            path = Some(arg);
+        } else {
+            return Err(format!("unexpected argument {}", arg).into());
+        }
+    }
+    Ok(path)
+}
+
+/* Contents of the input file, or of stdin without a file. */
+pub fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+/* }}} */
+/* {{{ XorShift */
+
+/* Pseudo-random numbers for generated inputs: the same seed always generates the same numbers. */
+pub struct XorShift {
+    /* never 0 */
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        XorShift { state: seed | 1 }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /* number from 0 to max excluded */
+    pub fn below(&mut self, max: u32) -> u32 {
+        (self.next_u64() % u64::from(max)) as u32
+    }
+
+    /* points of a square, both coordinates from 0 to size excluded */
+    pub fn points(&mut self, nb_points: u32, size: u32) -> Vec<(u32, u32)> {
+        (0..nb_points)
+            .map(|_| {
+                let x = self.below(size);
+                (x, self.below(size))
+            })
+            .collect()
+    }
+}
+
+impl Default for XorShift {
+    fn default() -> Self {
+        XorShift::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+/* }}} */
+/* {{{ ParseError */
+
+/* Error raised when a line of input does not match the expected pattern. */
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /* line number in the input, starting at 1, if known */
+    pub line: Option<usize>,
+    /* offending text */
+    pub text: String,
+    /* pattern the text was expected to match */
+    pub pattern: &'static str,
+    /* field of the pattern that could not be parsed, if known */
+    pub field: Option<&'static str>,
+}
+
+impl ParseError {
+    pub fn new(text: &str, pattern: &'static str) -> Self {
+        ParseError {
+            line: None,
+            text: text.to_owned(),
+            pattern,
+            field: None,
+        }
+    }
+
+    pub fn with_field(mut self, field: &'static str) -> Self {
+        self.field = Some(field);
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        match self.field {
+            Some(field) => write!(f, "invalid {} in {:?}", field, self.text)?,
+            None => write!(f, "cannot parse {:?}", self.text)?,
+        }
+        write!(f, ", expected {:?}", self.pattern)
+    }
+}
+
+impl Error for ParseError {}
+
+/* Unwrap a field extracted with scan_fmt!, or report which field of the pattern failed. */
+pub fn field<T>(
+    value: Option<T>,
+    name: &'static str,
+    text: &str,
+    pattern: &'static str,
+) -> std::result::Result<T, ParseError> {
+    value.ok_or_else(|| ParseError::new(text, pattern).with_field(name))
+}
+
+/* Parse every line of the input, tagging errors with the line number. */
+pub fn parse_lines<T>(input: &str) -> std::result::Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.parse().map_err(|e: ParseError| e.at_line(idx + 1)))
+        .collect()
+}
+
+/* }}} */
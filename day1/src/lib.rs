@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use common::Solution;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Default)]
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>> {
+        let mut changes = Vec::new();
+
+        for line in input.lines() {
+            changes.push(line.parse::<i32>()?);
+        }
+        Ok(changes)
+    }
+
+    fn part1(&self, changes: &Vec<i32>) -> Result<i32> {
+        Ok(changes.iter().sum())
+    }
+
+    fn part2(&self, changes: &Vec<i32>) -> Result<i32> {
+        let mut acc = 0;
+        /* the starting frequency counts as already reached */
+        let mut map: HashSet<i32> = [0].iter().cloned().collect();
+
+        if changes.is_empty() {
+            return Err(Box::from("no frequency changes"));
+        }
+        loop {
+            for change in changes {
+                acc += change;
+                if !map.insert(acc) {
+                    return Ok(acc);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::iter::Iterator;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use scan_fmt::scan_fmt;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+const NO_MESSAGE: &str = "points never converge into a message";
+
+pub struct Day10 {
+    /* the points are only rendered if their bounding box is smaller than this */
+    pub max_size: usize,
+}
+
+impl Default for Day10 {
+    fn default() -> Self {
+        Day10 { max_size: 100 }
+    }
+}
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Point>;
+    type Answer1 = String;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Vec<Point>> {
+        let points: Vec<Point> = parse_lines(input)?;
+
+        if points.is_empty() {
+            return Err(Box::from("no points in the input"));
+        }
+        Ok(points)
+    }
+
+    fn part1(&self, points: &Vec<Point>) -> Result<String> {
+        let mut points = points.clone();
+        simulate_time(&mut points)?;
+
+        let (xmin, xmax, ymin, ymax) = compute_bounding_box_size(&points);
+        let width = (xmax - xmin) as usize;
+        let height = (ymax - ymin) as usize;
+
+        // only render the points if the bounding box is small enough
+        if width >= self.max_size || height >= self.max_size {
+            return Err(Box::from(NO_MESSAGE));
+        }
+        Ok(display_points(&points, xmin, width, ymin, height))
+    }
+
+    fn part2(&self, points: &Vec<Point>) -> Result<u32> {
+        simulate_time(&mut points.clone())
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "max-size" => self.max_size = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 10", name).into()),
+        }
+        Ok(())
+    }
+}
+
+/* Step the points until their bounding box starts growing again. The points are left at the
+ * second where the bounding box was the smallest, and this second is returned.
+ *
+ * The width does not change when every point has the same horizontal velocity, and the height
+ * when every point has the same vertical velocity: the bounding box then only has to grow along
+ * the other dimension. When neither changes, the points never converge.
+ */
+fn simulate_time(points: &mut [Point]) -> Result<u32> {
+    let moving_x = points.iter().any(|p| p.vel_x != points[0].vel_x);
+    let moving_y = points.iter().any(|p| p.vel_y != points[0].vel_y);
+    if !moving_x && !moving_y {
+        return Err(Box::from(NO_MESSAGE));
+    }
+
+    let mut time = 0;
+    let mut prev_size = None;
+
+    loop {
+        let (xmin, xmax, ymin, ymax) = compute_bounding_box_size(points);
+        let width = (xmax - xmin) as usize;
+        let height = (ymax - ymin) as usize;
+
+        // loop until the bounding box increases
+        if let Some((prev_width, prev_height)) = prev_size {
+            if (!moving_x || width > prev_width) && (!moving_y || height > prev_height) {
+                for p in points.iter_mut() {
+                    p.step_back();
+                }
+                return Ok(time - 1);
+            }
+        }
+        prev_size = Some((width, height));
+
+        for p in points.iter_mut() {
+            p.step();
+        }
+        time += 1;
+    }
+}
+
+fn compute_bounding_box_size(points: &[Point]) -> (i32, i32, i32, i32) {
+    let mut iter = points.iter();
+    let first_point = iter.next().unwrap();
+    let mut xmin = first_point.x;
+    let mut xmax = first_point.x;
+    let mut ymin = first_point.y;
+    let mut ymax = first_point.y;
+
+    for p in iter {
+        if p.x < xmin {
+            xmin = p.x;
+        }
+        if p.x > xmax {
+            xmax = p.x;
+        }
+        if p.y < ymin {
+            ymin = p.y;
+        }
+        if p.y > ymax {
+            ymax = p.y;
+        }
+    }
+
+    (xmin, xmax, ymin, ymax)
+}
+
+fn display_points(points: &[Point], xmin: i32, width: usize, ymin: i32, height: usize) -> String {
+    let mut grid = vec![vec!['.'; width + 1]; height + 1];
+
+    for p in points {
+        grid[(p.y - ymin) as usize][(p.x - xmin) as usize] = '#';
+    }
+
+    let rows: Vec<String> = grid.into_iter().map(|row| row.into_iter().collect()).collect();
+    rows.join("\n")
+}
+
+/* {{{ Point */
+
+#[derive(Clone)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    vel_x: i32,
+    vel_y: i32,
+}
+
+const POINT_PATTERN: &str = "position=<{}, {}> velocity=<{}, {}>";
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (x, y, vel_x, vel_y) = scan_fmt!(s, POINT_PATTERN, i32, i32, i32, i32);
+
+        Ok(Point {
+            x: field(x, "x", s, POINT_PATTERN)?,
+            y: field(y, "y", s, POINT_PATTERN)?,
+            vel_x: field(vel_x, "x velocity", s, POINT_PATTERN)?,
+            vel_y: field(vel_y, "y velocity", s, POINT_PATTERN)?,
+        })
+    }
+}
+
+impl Point {
+    fn step(&mut self) {
+        self.x += self.vel_x;
+        self.y += self.vel_y;
+    }
+
+    fn step_back(&mut self) {
+        self.x -= self.vel_x;
+        self.y -= self.vel_y;
+    }
+}
+
+/* }}} */
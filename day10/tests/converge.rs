@@ -0,0 +1,31 @@
+use aoc18_rust_day10::Day10;
+use common::Solution;
+
+#[test]
+fn points_that_never_converge() {
+    let inputs = [
+        "position=< 1,  1> velocity=< 0,  0>\n",
+        "position=< 1,  1> velocity=< 0,  0>\nposition=< 5,  3> velocity=< 0,  0>\n",
+        "position=< 1,  1> velocity=< 2, -1>\nposition=< 5,  3> velocity=< 2, -1>\n",
+    ];
+
+    for input in &inputs {
+        let day10 = Day10::default();
+        let points = day10.parse(input).unwrap();
+
+        let err = day10.part1(&points).err().unwrap();
+        assert_eq!(err.to_string(), "points never converge into a message");
+        assert!(day10.part2(&points).is_err());
+    }
+}
+
+#[test]
+fn message_too_large() {
+    let day10 = Day10 { max_size: 3 };
+    let points = day10
+        .parse("position=< 0,  0> velocity=< 1,  0>\nposition=< 5,  0> velocity=< 2,  0>\n")
+        .unwrap();
+
+    assert!(day10.part1(&points).is_err());
+    assert_eq!(day10.part2(&points).unwrap(), 0);
+}
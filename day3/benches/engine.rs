@@ -0,0 +1,63 @@
+/* Compare the overlap engines on the committed input, and on generated fabrics with more and
+ * larger claims.
+ *
+ * Run with `cargo bench -p aoc18-rust-day3`.
+ */
+use std::fs;
+use std::time::Instant;
+
+use aoc18_rust_day3::{Day3, Engine};
+use common::{Solution, XorShift};
+
+const RUNS: u32 = 3;
+
+/* Claims up to max_claim_size wide and high, anywhere within the fabric */
+fn generate_claims(nb_claims: u32, fabric_size: u32, max_claim_size: u32) -> String {
+    let mut rng = XorShift::default();
+
+    let mut claims = String::new();
+    for id in 1..=nb_claims {
+        let width = 1 + rng.below(max_claim_size);
+        let height = 1 + rng.below(max_claim_size);
+        let x = rng.below(fabric_size - width);
+        let y = rng.below(fabric_size - height);
+        claims.push_str(&format!("#{} @ {},{}: {}x{}\n", id, x, y, width, height));
+    }
+    claims
+}
+
+fn bench(name: &str, input: &str) {
+    for engine in &[Engine::Map, Engine::Dense, Engine::Sweep] {
+        let day3 = Day3 {
+            engine: *engine,
+            ..Default::default()
+        };
+        let fabric = day3.parse(input).unwrap();
+        let start = Instant::now();
+        let mut answers = (0, String::new());
+
+        for _ in 0..RUNS {
+            answers = (
+                day3.part1(&fabric).unwrap(),
+                day3.part2(&fabric)
+                    .map_or_else(|e| e.to_string(), |id| id.to_string()),
+            );
+        }
+        println!(
+            "{}, {:?}: overlaps {}, uncontested {}, {:?} per run",
+            name,
+            engine,
+            answers.0,
+            answers.1,
+            start.elapsed() / RUNS
+        );
+    }
+}
+
+fn main() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+
+    bench("input", &input);
+    bench("10k claims", &generate_claims(10_000, 3_000, 100));
+    bench("1k large claims", &generate_claims(1_000, 2_000, 300));
+}
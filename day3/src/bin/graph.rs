@@ -0,0 +1,34 @@
+/* Build the interference graph of the claims, print its connected components and largest
+ * clique, and export it.
+ *
+ * usage: graph [--output <file.dot|file.json>] [<file>]
+ *
+ * Without a file, the claims are read from stdin.
+ */
+use std::env;
+use std::path::PathBuf;
+
+use aoc18_rust_day3::{Day3, OverlapGraph, Result};
+use common::{parse_args, read_input, Solution};
+
+fn main() -> Result<()> {
+    let mut output = None;
+
+    let path = parse_args(env::args().skip(1), |option, args| {
+        match option {
+            "--output" => output = Some(PathBuf::from(args.next().ok_or("missing output file")?)),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let input = read_input(path.as_deref())?;
+
+    let fabric = Day3::default().parse(&input)?;
+    let graph = OverlapGraph::new(fabric.claims());
+
+    if let Some(output) = output {
+        graph.save(&output)?;
+    }
+    println!("{}", graph.summary());
+    Ok(())
+}
@@ -0,0 +1,39 @@
+/* Draw the claims of the fabric, colored by the number of claims over each square inch, with
+ * the uncontested claim highlighted.
+ *
+ * usage: render [--output <file.png|file.ppm>] [--columns <nb>] [<file>]
+ *
+ * A preview at most --columns characters wide (80 by default, 0 to disable) is printed for
+ * 24-bit color terminals. Without a file, the claims are read from stdin.
+ */
+use std::env;
+use std::path::PathBuf;
+
+use aoc18_rust_day3::{Day3, DenseGrid, Grid, Heatmap, Result};
+use common::{parse_args, read_input, Solution};
+
+fn main() -> Result<()> {
+    let mut output = None;
+    let mut columns = 80;
+
+    let path = parse_args(env::args().skip(1), |option, args| {
+        match option {
+            "--output" => output = Some(PathBuf::from(args.next().ok_or("missing output file")?)),
+            "--columns" => columns = args.next().ok_or("missing number of columns")?.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let input = read_input(path.as_deref())?;
+
+    let fabric = Day3::default().parse(&input)?;
+    let heatmap = Heatmap::new(fabric.claims(), &DenseGrid::build(fabric.claims()));
+
+    if let Some(output) = output {
+        heatmap.save(&output)?;
+    }
+    if columns > 0 {
+        println!("{}", heatmap.to_ansi(columns));
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use crate::Claim;
+
+/* Number of claims over each square inch of the fabric */
+pub trait Grid: Sized {
+    fn build(claims: &[Claim]) -> Self;
+
+    /* number of claims over the square inch at (x, y) */
+    fn count(&self, x: u32, y: u32) -> u32;
+
+    /* number of square inches within two or more claims */
+    fn count_overlaps(&self) -> usize;
+
+    /* id of the first claim whose square inches are all claimed once */
+    fn find_uncontested(&self, claims: &[Claim]) -> Option<u32> {
+        claims
+            .iter()
+            .find(|claim| claim.iter_points().all(|(x, y)| self.count(x, y) == 1))
+            .map(|claim| claim.id)
+    }
+}
+
+/* {{{ Map grid */
+
+/* One entry per claimed square inch. */
+pub struct MapGrid(HashMap<(u32, u32), u32>);
+
+impl Grid for MapGrid {
+    fn build(claims: &[Claim]) -> Self {
+        let mut grid = HashMap::new();
+
+        for claim in claims {
+            for (x, y) in claim.iter_points() {
+                *grid.entry((x, y)).or_insert(0) += 1;
+            }
+        }
+        MapGrid(grid)
+    }
+
+    fn count(&self, x: u32, y: u32) -> u32 {
+        self.0.get(&(x, y)).copied().unwrap_or(0)
+    }
+
+    fn count_overlaps(&self) -> usize {
+        self.0.values().filter(|x| **x > 1).count()
+    }
+}
+
+/* }}} */
+/* {{{ Dense grid */
+
+/* One counter per square inch of the bounding box of the claims, row by row. */
+pub struct DenseGrid {
+    /* top left corner of the bounding box */
+    x0: u32,
+    y0: u32,
+    width: u32,
+    height: u32,
+    counts: Vec<u32>,
+}
+
+impl DenseGrid {
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.x0 || y < self.y0 {
+            return None;
+        }
+        let (dx, dy) = (x - self.x0, y - self.y0);
+        if dx >= self.width || dy >= self.height {
+            return None;
+        }
+        Some(dy as usize * self.width as usize + dx as usize)
+    }
+}
+
+impl Grid for DenseGrid {
+    fn build(claims: &[Claim]) -> Self {
+        let claims = claims.iter().filter(|c| c.width > 0 && c.height > 0);
+        let x0 = claims.clone().map(|c| c.x).min().unwrap_or(0);
+        let y0 = claims.clone().map(|c| c.y).min().unwrap_or(0);
+        let x1 = claims.clone().map(|c| c.x + c.width).max().unwrap_or(0);
+        let y1 = claims.clone().map(|c| c.y + c.height).max().unwrap_or(0);
+
+        let mut grid = DenseGrid {
+            x0,
+            y0,
+            width: x1 - x0,
+            height: y1 - y0,
+            counts: Vec::new(),
+        };
+        grid.counts = vec![0; grid.width as usize * grid.height as usize];
+
+        for claim in claims {
+            for y in claim.y..(claim.y + claim.height) {
+                /* cannot fail, the claim is within the bounding box */
+                let start = grid.index(claim.x, y).unwrap();
+                for count in &mut grid.counts[start..(start + claim.width as usize)] {
+                    *count += 1;
+                }
+            }
+        }
+        grid
+    }
+
+    fn count(&self, x: u32, y: u32) -> u32 {
+        self.index(x, y).map_or(0, |idx| self.counts[idx])
+    }
+
+    fn count_overlaps(&self) -> usize {
+        self.counts.iter().filter(|x| **x > 1).count()
+    }
+}
+
+/* }}} */
+/* {{{ Sweep line */
+
+/* Rectangle of square inches, from (x, y) included to (x2, y2) excluded */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub x2: u32,
+    pub y2: u32,
+}
+
+impl Rect {
+    pub fn area(&self) -> u64 {
+        u64::from(self.x2 - self.x) * u64::from(self.y2 - self.y)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x >= self.x2 || self.y >= self.y2
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            x2: self.x2.min(other.x2),
+            y2: self.y2.min(other.y2),
+        };
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+}
+
+/* Area within two or more claims. */
+pub fn sweep_overlaps(claims: &[Claim]) -> u64 {
+    let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
+
+    covered_area(&rects, 2)
+}
+
+/* Area within min_depth or more rectangles, computed by sweeping a vertical line over the left
+ * and right edges of the rectangles. Between two consecutive edges, the rectangles crossing the
+ * line do not change, and the covered length of the line is found by sorting their vertical
+ * intervals.
+ */
+pub(crate) fn covered_area(rects: &[Rect], min_depth: i32) -> u64 {
+    /* (x, rectangle starts, rectangle index), ends sorted before starts */
+    let mut events: Vec<(u32, bool, usize)> = Vec::with_capacity(rects.len() * 2);
+    for (idx, rect) in rects.iter().enumerate() {
+        if !rect.is_empty() {
+            events.push((rect.x, true, idx));
+            events.push((rect.x2, false, idx));
+        }
+    }
+    events.sort_unstable();
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut area = 0;
+    let mut prev_x = 0;
+
+    for (x, starts, idx) in events {
+        if x > prev_x && active.len() >= min_depth as usize {
+            area += u64::from(x - prev_x) * covered_length(rects, &active, min_depth);
+        }
+        prev_x = x;
+
+        if starts {
+            active.push(idx);
+        } else if let Some(pos) = active.iter().position(|&i| i == idx) {
+            active.swap_remove(pos);
+        }
+    }
+    area
+}
+
+/* Length covered by min_depth or more of the vertical intervals of the active rectangles. */
+fn covered_length(rects: &[Rect], active: &[usize], min_depth: i32) -> u64 {
+    /* (y, +1 on start or -1 on end), ends sorted before starts */
+    let mut bounds: Vec<(u32, i32)> = Vec::with_capacity(active.len() * 2);
+    for &idx in active {
+        bounds.push((rects[idx].y, 1));
+        bounds.push((rects[idx].y2, -1));
+    }
+    bounds.sort_unstable();
+
+    let mut length = 0;
+    let mut depth = 0;
+    let mut prev_y = 0;
+    for (y, delta) in bounds {
+        if depth >= min_depth {
+            length += u64::from(y - prev_y);
+        }
+        depth += delta;
+        prev_y = y;
+    }
+    length
+}
+
+/* Indexes of every pair of overlapping claims, the lowest index first. The claims are swept from
+ * left to right, and only compared with the claims still crossing the sweep line.
+ */
+pub(crate) fn overlapping_pairs(claims: &[Claim]) -> Vec<(usize, usize)> {
+    let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
+    let mut order: Vec<usize> = (0..rects.len()).filter(|&i| !rects[i].is_empty()).collect();
+    order.sort_unstable_by_key(|&idx| rects[idx].x);
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+
+    for idx in order {
+        let rect = &rects[idx];
+
+        active.retain(|&other| rects[other].x2 > rect.x);
+        for &other in &active {
+            if rect.y < rects[other].y2 && rects[other].y < rect.y2 {
+                pairs.push((idx.min(other), idx.max(other)));
+            }
+        }
+        active.push(idx);
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/* Id of the first claim not overlapping any other claim. */
+pub fn sweep_uncontested(claims: &[Claim]) -> Option<u32> {
+    let mut contested = vec![false; claims.len()];
+
+    for (a, b) in overlapping_pairs(claims) {
+        contested[a] = true;
+        contested[b] = true;
+    }
+    claims
+        .iter()
+        .zip(contested)
+        .find(|(_, contested)| !contested)
+        .map(|(claim, _)| claim.id)
+}
+
+/* }}} */
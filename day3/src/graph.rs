@@ -0,0 +1,244 @@
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::engine::{overlapping_pairs, Rect};
+use crate::Claim;
+
+/* Interference graph of the claims: one node per claim, and an edge between each pair of
+ * overlapping claims, weighted by the area they share.
+ */
+pub struct OverlapGraph {
+    ids: Vec<u32>,
+    rects: Vec<Rect>,
+    /* (node, node, overlapped area), the lowest node first */
+    edges: Vec<(usize, usize, u64)>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl OverlapGraph {
+    pub fn new(claims: &[Claim]) -> Self {
+        let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
+        let mut neighbours = vec![Vec::new(); claims.len()];
+
+        let edges = overlapping_pairs(claims)
+            .into_iter()
+            .map(|(a, b)| {
+                neighbours[a].push(b);
+                neighbours[b].push(a);
+                /* cannot fail, the claims overlap */
+                (a, b, rects[a].intersection(&rects[b]).unwrap().area())
+            })
+            .collect();
+
+        OverlapGraph {
+            ids: claims.iter().map(|c| c.id).collect(),
+            rects,
+            edges,
+            neighbours,
+        }
+    }
+
+    /* claim ids of both ends of each edge, with the overlapped area */
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32, u64)> + '_ {
+        self.edges
+            .iter()
+            .map(move |&(a, b, area)| (self.ids[a], self.ids[b], area))
+    }
+
+    /* Claim ids of each connected component, components and ids in input order. */
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        let mut component = vec![None; self.ids.len()];
+        let mut components = Vec::new();
+
+        for start in 0..self.ids.len() {
+            if component[start].is_some() {
+                continue;
+            }
+            let mut nodes = vec![start];
+            let mut todo = vec![start];
+            component[start] = Some(components.len());
+
+            while let Some(node) = todo.pop() {
+                for &next in &self.neighbours[node] {
+                    if component[next].is_none() {
+                        component[next] = Some(components.len());
+                        nodes.push(next);
+                        todo.push(next);
+                    }
+                }
+            }
+            nodes.sort_unstable();
+            components.push(nodes.into_iter().map(|n| self.ids[n]).collect());
+        }
+        components
+    }
+
+    /* Claim ids of a largest set of claims all overlapping each other, in input order.
+     *
+     * Rectangles overlapping each other pairwise all share a square inch, so the largest clique
+     * is the set of claims over the most claimed square inch. It is found by sweeping a vertical
+     * line over the left edges of the claims, where the most claimed square inches can be found.
+     */
+    pub fn max_clique(&self) -> Vec<u32> {
+        let mut order: Vec<usize> = (0..self.rects.len())
+            .filter(|&i| !self.rects[i].is_empty())
+            .collect();
+        order.sort_unstable_by_key(|&idx| self.rects[idx].x);
+
+        let mut best: Vec<usize> = Vec::new();
+        let mut active: Vec<usize> = Vec::new();
+
+        for (pos, &idx) in order.iter().enumerate() {
+            let x = self.rects[idx].x;
+
+            active.retain(|&other| self.rects[other].x2 > x);
+            active.push(idx);
+            /* all the claims starting at x are active before looking at the line */
+            if order.get(pos + 1).map(|&next| self.rects[next].x) == Some(x) {
+                continue;
+            }
+
+            let clique = deepest_interval(&self.rects, &active);
+            if clique.len() > best.len() {
+                best = clique;
+            }
+        }
+        best.sort_unstable();
+        best.into_iter().map(|n| self.ids[n]).collect()
+    }
+
+    pub fn summary(&self) -> GraphSummary {
+        let components = self.components();
+
+        GraphSummary {
+            nb_claims: self.ids.len(),
+            nb_edges: self.edges.len(),
+            nb_components: components.len(),
+            nb_isolated: components.iter().filter(|c| c.len() == 1).count(),
+            largest_component: components.iter().map(Vec::len).max().unwrap_or(0),
+            max_clique: self.max_clique(),
+        }
+    }
+
+    pub fn write_dot<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "graph claims {{")?;
+        for id in &self.ids {
+            writeln!(out, "    {};", id)?;
+        }
+        for (a, b, area) in self.edges() {
+            writeln!(
+                out,
+                "    {} -- {} [weight={}, label=\"{}\"];",
+                a, b, area, area
+            )?;
+        }
+        writeln!(out, "}}")
+    }
+
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let join = |ids: &[u32]| {
+            let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            format!("[{}]", ids.join(","))
+        };
+        let edges: Vec<String> = self
+            .edges()
+            .map(|(a, b, area)| format!("{{\"source\":{},\"target\":{},\"area\":{}}}", a, b, area))
+            .collect();
+        let components: Vec<String> = self.components().iter().map(|c| join(c)).collect();
+
+        writeln!(
+            out,
+            "{{\"nodes\":{},\"edges\":[{}],\"components\":[{}],\"max_clique\":{}}}",
+            join(&self.ids),
+            edges.join(","),
+            components.join(","),
+            join(&self.max_clique())
+        )
+    }
+
+    /* Export the graph to a file, as DOT or JSON depending on its extension. */
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let write = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("dot") | Some("gv") => Self::write_dot,
+            Some("json") => Self::write_json,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "expected a .dot, .gv or .json file",
+                ))
+            }
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+
+        write(self, &mut out)?;
+        out.flush()
+    }
+}
+
+/* Nodes of the active rectangles whose vertical intervals share the most claimed square inch. */
+fn deepest_interval(rects: &[Rect], active: &[usize]) -> Vec<usize> {
+    /* (y, +1 on start or -1 on end), ends sorted before starts */
+    let mut bounds: Vec<(u32, i32)> = Vec::with_capacity(active.len() * 2);
+    for &idx in active {
+        bounds.push((rects[idx].y, 1));
+        bounds.push((rects[idx].y2, -1));
+    }
+    bounds.sort_unstable();
+
+    let mut depth = 0;
+    let mut best = (0, 0);
+    for (y, delta) in bounds {
+        depth += delta;
+        if depth > best.0 {
+            best = (depth, y);
+        }
+    }
+
+    let y = best.1;
+    active
+        .iter()
+        .copied()
+        .filter(|&idx| rects[idx].y <= y && y < rects[idx].y2)
+        .collect()
+}
+
+/* Connected components and largest clique of an overlap graph */
+pub struct GraphSummary {
+    pub nb_claims: usize,
+    pub nb_edges: usize,
+    pub nb_components: usize,
+    /* components made of a single claim, overlapping no other claim */
+    pub nb_isolated: usize,
+    pub largest_component: usize,
+    pub max_clique: Vec<u32>,
+}
+
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clique: Vec<String> = self
+            .max_clique
+            .iter()
+            .map(|id| format!("#{}", id))
+            .collect();
+
+        writeln!(
+            f,
+            "{} claims, {} overlapping pairs",
+            self.nb_claims, self.nb_edges
+        )?;
+        writeln!(
+            f,
+            "{} components, the largest with {} claims, {} isolated claims",
+            self.nb_components, self.largest_component, self.nb_isolated
+        )?;
+        write!(
+            f,
+            "largest clique: {} claims: {}",
+            self.max_clique.len(),
+            clique.join(", ")
+        )
+    }
+}
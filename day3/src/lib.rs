@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::fmt;
+use std::iter::Iterator;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use scan_fmt::scan_fmt;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+mod engine;
+mod graph;
+mod query;
+mod render;
+
+use engine::Rect;
+pub use engine::{DenseGrid, Grid, MapGrid};
+pub use graph::{GraphSummary, OverlapGraph};
+pub use query::{claim_overlaps, uncontested_claims, ClaimOverlaps};
+pub use render::Heatmap;
+
+#[derive(Default)]
+pub struct Day3 {
+    /* how the overlapping square inches are computed */
+    pub engine: Engine,
+    /* report the overlaps of every claim in part2, and not only the first uncontested claim */
+    pub report: bool,
+}
+
+pub struct Fabric {
+    claims: Vec<Claim>,
+}
+
+impl Fabric {
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+}
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Fabric;
+    type Answer1 = usize;
+    type Answer2 = Uncontested;
+
+    fn parse(&self, input: &str) -> Result<Fabric> {
+        let claims: Vec<Claim> = parse_lines(input)?;
+
+        Ok(Fabric { claims })
+    }
+
+    fn part1(&self, fabric: &Fabric) -> Result<usize> {
+        Ok(count_overlaps(&fabric.claims, self.engine))
+    }
+
+    fn part2(&self, fabric: &Fabric) -> Result<Uncontested> {
+        if self.report {
+            return Ok(Uncontested::Report(claim_overlaps(&fabric.claims)));
+        }
+        find_uncontested(&fabric.claims, self.engine)
+            .map(Uncontested::First)
+            .ok_or_else(|| Box::<dyn Error>::from("no uncontested claims"))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "engine" => self.engine = value.parse()?,
+            "report" => self.report = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 3", name).into()),
+        }
+        Ok(())
+    }
+}
+
+/* Id of the first uncontested claim, or the overlaps of every claim, one per line */
+pub enum Uncontested {
+    First(u32),
+    Report(Vec<ClaimOverlaps>),
+}
+
+impl fmt::Display for Uncontested {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Uncontested::First(id) => write!(f, "{}", id),
+            Uncontested::Report(claims) => {
+                let rows: Vec<String> = claims.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", rows.join("\n"))
+            }
+        }
+    }
+}
+
+/* {{{ Engine */
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Engine {
+    /* one hash map entry per claimed square inch, kept for comparison */
+    Map,
+    /* one counter per square inch of the bounding box of the claims */
+    Dense,
+    /* sweep line over the claim edges, without materializing the square inches */
+    #[default]
+    Sweep,
+}
+
+impl FromStr for Engine {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "map" => Ok(Engine::Map),
+            "dense" => Ok(Engine::Dense),
+            "sweep" => Ok(Engine::Sweep),
+            _ => Err(format!("unknown engine {}, expected map, dense or sweep", s).into()),
+        }
+    }
+}
+
+/* Number of square inches within two or more claims. */
+pub fn count_overlaps(claims: &[Claim], engine: Engine) -> usize {
+    match engine {
+        Engine::Map => MapGrid::build(claims).count_overlaps(),
+        Engine::Dense => DenseGrid::build(claims).count_overlaps(),
+        Engine::Sweep => engine::sweep_overlaps(claims) as usize,
+    }
+}
+
+/* Id of the first claim not overlapping any other claim. */
+pub fn find_uncontested(claims: &[Claim], engine: Engine) -> Option<u32> {
+    match engine {
+        Engine::Map => MapGrid::build(claims).find_uncontested(claims),
+        Engine::Dense => DenseGrid::build(claims).find_uncontested(claims),
+        Engine::Sweep => engine::sweep_uncontested(claims),
+    }
+}
+
+/* }}} */
+/* {{{ Claim */
+
+pub struct Claim {
+    id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/* "#{id} @ {x},{y}: {width}x{height}" */
+const CLAIM_PATTERN: &str = "#{d} @ {d},{d}: {d}x{d}";
+
+impl FromStr for Claim {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (id, x, y, w, h) = scan_fmt!(s, CLAIM_PATTERN, u32, u32, u32, u32, u32);
+        let x = field(x, "x", s, CLAIM_PATTERN)?;
+        let y = field(y, "y", s, CLAIM_PATTERN)?;
+        let width = field(w, "width", s, CLAIM_PATTERN)?;
+        let height = field(h, "height", s, CLAIM_PATTERN)?;
+
+        /* the far edges of the claim must fit as well */
+        field(x.checked_add(width), "width", s, CLAIM_PATTERN)?;
+        field(y.checked_add(height), "height", s, CLAIM_PATTERN)?;
+
+        Ok(Claim {
+            id: field(id, "id", s, CLAIM_PATTERN)?,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+impl Claim {
+    fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            x2: self.x + self.width,
+            y2: self.y + self.height,
+        }
+    }
+
+    fn iter_points(&self) -> ClaimIterator {
+        ClaimIterator {
+            x: self.x,
+            y: self.y,
+            x2: self.x + self.width,
+            y2: self.y + self.height,
+            yiter: self.y,
+        }
+    }
+}
+
+struct ClaimIterator {
+    x: u32,
+    x2: u32,
+    y: u32,
+    y2: u32,
+    yiter: u32,
+}
+
+impl Iterator for ClaimIterator {
+    type Item = (u32, u32);
+
+    fn next(&mut self) -> Option<(u32, u32)> {
+        /* current point */
+        let (x, y) = (self.x, self.yiter);
+        if self.x >= self.x2 || self.yiter >= self.y2 {
+            return None;
+        }
+        /* advance */
+        self.yiter += 1;
+        if self.yiter >= self.y2 {
+            self.yiter = self.y;
+            self.x += 1;
+        }
+        Some((x, y))
+    }
+}
+
+/* }}} */
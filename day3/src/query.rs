@@ -0,0 +1,81 @@
+use std::fmt;
+
+use crate::engine::{covered_area, overlapping_pairs, Rect};
+use crate::Claim;
+
+/* How a claim overlaps the other claims */
+#[derive(Debug, PartialEq)]
+pub struct ClaimOverlaps {
+    pub id: u32,
+    /* ids of the claims overlapping this one, with the area they share with it, in input order */
+    pub overlaps: Vec<(u32, u64)>,
+    /* square inches of the claim within one or more other claims */
+    pub overlapped_area: u64,
+}
+
+impl ClaimOverlaps {
+    pub fn is_uncontested(&self) -> bool {
+        self.overlaps.is_empty()
+    }
+}
+
+impl fmt::Display for ClaimOverlaps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_uncontested() {
+            return write!(f, "#{}: uncontested", self.id);
+        }
+
+        let overlaps: Vec<String> = self
+            .overlaps
+            .iter()
+            .map(|(id, area)| format!("#{} ({})", id, area))
+            .collect();
+        write!(
+            f,
+            "#{}: {} overlapped by {}",
+            self.id,
+            self.overlapped_area,
+            overlaps.join(", ")
+        )
+    }
+}
+
+/* Overlaps of every claim, in input order. */
+pub fn claim_overlaps(claims: &[Claim]) -> Vec<ClaimOverlaps> {
+    let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
+    let mut intersections: Vec<Vec<(usize, Rect)>> = vec![Vec::new(); claims.len()];
+
+    for (a, b) in overlapping_pairs(claims) {
+        /* cannot fail, the claims overlap */
+        let rect = rects[a].intersection(&rects[b]).unwrap();
+        intersections[a].push((b, rect));
+        intersections[b].push((a, rect));
+    }
+
+    claims
+        .iter()
+        .zip(intersections)
+        .map(|(claim, mut intersections)| {
+            intersections.sort_unstable_by_key(|&(idx, _)| idx);
+            let rects: Vec<Rect> = intersections.iter().map(|&(_, rect)| rect).collect();
+
+            ClaimOverlaps {
+                id: claim.id,
+                overlaps: intersections
+                    .iter()
+                    .map(|&(idx, rect)| (claims[idx].id, rect.area()))
+                    .collect(),
+                overlapped_area: covered_area(&rects, 1),
+            }
+        })
+        .collect()
+}
+
+/* Ids of every claim not overlapping any other claim, in input order. */
+pub fn uncontested_claims(claims: &[Claim]) -> Vec<u32> {
+    claim_overlaps(claims)
+        .into_iter()
+        .filter(ClaimOverlaps::is_uncontested)
+        .map(|overlaps| overlaps.id)
+        .collect()
+}
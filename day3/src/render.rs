@@ -0,0 +1,185 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::{Claim, Grid};
+
+type Color = [u8; 3];
+
+const BACKGROUND: Color = [16, 16, 16];
+const CLAIMED_ONCE: Color = [40, 60, 110];
+const UNCONTESTED: Color = [0, 220, 80];
+/* overlaps go from yellow, for two claims, to red for the most claimed square inches */
+const OVERLAP_LOW: Color = [255, 210, 0];
+const OVERLAP_HIGH: Color = [220, 0, 0];
+
+/* Number of claims over each square inch of the fabric, from its top left corner to the
+ * bottom right corner of the claims, with the uncontested claim highlighted.
+ */
+pub struct Heatmap {
+    width: u32,
+    height: u32,
+    /* row by row */
+    counts: Vec<u32>,
+    highlighted: Vec<bool>,
+    /* largest count of the fabric, kept when downscaling so that colors do not change */
+    max_count: u32,
+}
+
+impl Heatmap {
+    pub fn new<G: Grid>(claims: &[Claim], grid: &G) -> Self {
+        let width = claims.iter().map(|c| c.x + c.width).max().unwrap_or(0);
+        let height = claims.iter().map(|c| c.y + c.height).max().unwrap_or(0);
+
+        let mut counts = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                counts.push(grid.count(x, y));
+            }
+        }
+
+        let mut highlighted = vec![false; counts.len()];
+        let uncontested = grid.find_uncontested(claims);
+        for claim in claims.iter().filter(|c| Some(c.id) == uncontested) {
+            for (x, y) in claim.iter_points() {
+                highlighted[y as usize * width as usize + x as usize] = true;
+            }
+        }
+
+        Heatmap {
+            width,
+            height,
+            max_count: counts.iter().copied().max().unwrap_or(0),
+            counts,
+            highlighted,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn color(&self, idx: usize) -> Color {
+        if self.highlighted[idx] {
+            return UNCONTESTED;
+        }
+        match self.counts[idx] {
+            0 => BACKGROUND,
+            1 => CLAIMED_ONCE,
+            count => {
+                let ratio = if self.max_count > 2 {
+                    f64::from(count - 2) / f64::from(self.max_count - 2)
+                } else {
+                    0.
+                };
+                let mut color = OVERLAP_LOW;
+                for (c, (low, high)) in color.iter_mut().zip(OVERLAP_LOW.iter().zip(&OVERLAP_HIGH))
+                {
+                    *c = (f64::from(*low) + (f64::from(*high) - f64::from(*low)) * ratio) as u8;
+                }
+                color
+            }
+        }
+    }
+
+    /* Shrink the heatmap, each pixel covering factor x factor square inches. A pixel gets the
+     * largest count of its square inches, so that small overlaps stay visible.
+     */
+    pub fn downscale(&self, factor: u32) -> Heatmap {
+        let factor = factor.max(1);
+        let width = self.width.div_ceil(factor);
+        let height = self.height.div_ceil(factor);
+        let mut counts = vec![0; width as usize * height as usize];
+        let mut highlighted = vec![false; counts.len()];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let src = y as usize * self.width as usize + x as usize;
+                let dst = (y / factor) as usize * width as usize + (x / factor) as usize;
+                counts[dst] = counts[dst].max(self.counts[src]);
+                highlighted[dst] |= self.highlighted[src];
+            }
+        }
+
+        Heatmap {
+            width,
+            height,
+            counts,
+            highlighted,
+            max_count: self.max_count,
+        }
+    }
+
+    fn rgb(&self) -> Vec<u8> {
+        (0..self.counts.len())
+            .flat_map(|idx| self.color(idx).to_vec())
+            .collect()
+    }
+
+    /* binary PPM (P6) */
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.rgb())
+    }
+
+    pub fn write_png<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb())?;
+        Ok(writer.finish()?)
+    }
+
+    /* Write the heatmap to a file, as PNG or PPM depending on its extension. */
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let write = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => Self::write_png,
+            Some("ppm") => Self::write_ppm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "expected a .png or .ppm file",
+                ))
+            }
+        };
+        let mut out = BufWriter::new(File::create(path)?);
+
+        write(self, &mut out)?;
+        out.flush()
+    }
+
+    /* Preview for 24-bit color terminals, at most columns characters wide. Each character
+     * draws two pixels, one above the other.
+     */
+    pub fn to_ansi(&self, columns: u32) -> String {
+        let preview = self.downscale(self.width.div_ceil(columns.max(1)));
+        let mut lines = Vec::new();
+
+        for y in (0..preview.height).step_by(2) {
+            let mut line = String::new();
+
+            for x in 0..preview.width {
+                let [r, g, b] = preview.color((y * preview.width + x) as usize);
+                line.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b));
+
+                if y + 1 < preview.height {
+                    let [r, g, b] = preview.color(((y + 1) * preview.width + x) as usize);
+                    line.push_str(&format!("\x1b[48;2;{};{};{}m", r, g, b));
+                } else {
+                    line.push_str("\x1b[49m");
+                }
+                line.push('\u{2580}');
+            }
+            line.push_str("\x1b[0m");
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
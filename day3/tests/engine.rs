@@ -0,0 +1,66 @@
+use std::fs;
+
+use aoc18_rust_day3::{
+    claim_overlaps, count_overlaps, find_uncontested, uncontested_claims, Day3, Engine,
+};
+use common::Solution;
+
+const ENGINES: [Engine; 3] = [Engine::Map, Engine::Dense, Engine::Sweep];
+
+fn check_engines(input: &str, overlaps: usize, uncontested: Option<u32>) {
+    let fabric = Day3::default().parse(input).unwrap();
+
+    for &engine in &ENGINES {
+        assert_eq!(
+            count_overlaps(fabric.claims(), engine),
+            overlaps,
+            "{:?}",
+            engine
+        );
+        assert_eq!(
+            find_uncontested(fabric.claims(), engine),
+            uncontested,
+            "{:?}",
+            engine
+        );
+    }
+}
+
+#[test]
+fn engines_match_on_committed_input() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+
+    check_engines(&input, 120408, Some(1276));
+}
+
+#[test]
+fn engines_handle_edge_cases() {
+    /* no claims */
+    check_engines("", 0, None);
+    /* claims touching without overlapping */
+    check_engines("#1 @ 0,0: 2x2\n#2 @ 2,0: 2x2\n#3 @ 0,2: 4x1\n", 0, Some(1));
+    /* a claim inside another, and three claims over the same square inches */
+    check_engines(
+        "#1 @ 1,1: 5x5\n#2 @ 2,2: 2x2\n#3 @ 2,2: 1x1\n#4 @ 9,9: 1x1\n",
+        4,
+        Some(4),
+    );
+    /* empty claims overlap nothing */
+    check_engines("#1 @ 1,1: 0x5\n#2 @ 0,0: 3x3\n", 0, Some(1));
+    /* far from the origin */
+    check_engines("#1 @ 1000,2000: 3x3\n#2 @ 1002,2002: 3x3\n", 1, None);
+}
+
+#[test]
+fn uncontested_claims_of_committed_input() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+    let fabric = Day3::default().parse(&input).unwrap();
+
+    assert_eq!(uncontested_claims(fabric.claims()), vec![1276]);
+
+    /* the overlapped square inches of every claim add up to at least the overlapped area */
+    let overlaps = claim_overlaps(fabric.claims());
+    let total: u64 = overlaps.iter().map(|c| c.overlapped_area).sum();
+    assert_eq!(overlaps.len(), fabric.claims().len());
+    assert!(total >= 120408);
+}
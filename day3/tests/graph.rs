@@ -0,0 +1,74 @@
+use std::fs;
+
+use aoc18_rust_day3::{Day3, DenseGrid, Grid, OverlapGraph};
+use common::Solution;
+
+/* #1, #2 and #3 overlap each other around (3, 3), #4 only overlaps #3, #5 overlaps nothing */
+const CLAIMS: &str = "#1 @ 0,0: 4x4\n#2 @ 2,2: 4x4\n#3 @ 3,3: 1x3\n#4 @ 3,5: 3x1\n#5 @ 9,0: 2x2\n";
+
+fn graph(input: &str) -> OverlapGraph {
+    let fabric = Day3::default().parse(input).unwrap();
+
+    OverlapGraph::new(fabric.claims())
+}
+
+#[test]
+fn edges_and_components() {
+    let graph = graph(CLAIMS);
+
+    let edges: Vec<(u32, u32, u64)> = graph.edges().collect();
+    assert_eq!(
+        edges,
+        vec![(1, 2, 4), (1, 3, 1), (2, 3, 3), (2, 4, 3), (3, 4, 1)]
+    );
+    assert_eq!(graph.components(), vec![vec![1, 2, 3, 4], vec![5]]);
+    assert_eq!(graph.max_clique(), vec![1, 2, 3]);
+}
+
+#[test]
+fn summary() {
+    assert_eq!(
+        graph(CLAIMS).summary().to_string(),
+        "5 claims, 5 overlapping pairs\n\
+         2 components, the largest with 4 claims, 1 isolated claims\n\
+         largest clique: 3 claims: #1, #2, #3"
+    );
+}
+
+#[test]
+fn exports() {
+    let graph = graph("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+
+    let mut dot = Vec::new();
+    graph.write_dot(&mut dot).unwrap();
+    assert_eq!(
+        String::from_utf8(dot).unwrap(),
+        "graph claims {\n    1;\n    2;\n    3;\n    1 -- 2 [weight=4, label=\"4\"];\n}\n"
+    );
+
+    let mut json = Vec::new();
+    graph.write_json(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "{\"nodes\":[1,2,3],\"edges\":[{\"source\":1,\"target\":2,\"area\":4}],\
+         \"components\":[[1,2],[3]],\"max_clique\":[1,2]}\n"
+    );
+}
+
+#[test]
+fn max_clique_of_committed_input() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+    let fabric = Day3::default().parse(&input).unwrap();
+
+    /* the largest clique is the set of claims over the most claimed square inch */
+    let grid = DenseGrid::build(fabric.claims());
+    let max_count = (0..1000)
+        .flat_map(|y| (0..1000).map(move |x| (x, y)))
+        .map(|(x, y)| grid.count(x, y))
+        .max()
+        .unwrap();
+    assert_eq!(
+        OverlapGraph::new(fabric.claims()).max_clique().len(),
+        max_count as usize
+    );
+}
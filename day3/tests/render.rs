@@ -0,0 +1,69 @@
+use aoc18_rust_day3::{Day3, DenseGrid, Grid, Heatmap, MapGrid};
+use common::Solution;
+
+const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+fn heatmap<G: Grid>(input: &str) -> Heatmap {
+    let fabric = Day3::default().parse(input).unwrap();
+
+    Heatmap::new(fabric.claims(), &G::build(fabric.claims()))
+}
+
+fn pixel(ppm: &[u8], width: usize, x: usize, y: usize) -> &[u8] {
+    /* skip the header: "P6\n7 7\n255\n" */
+    let start = 11 + (y * width + x) * 3;
+    &ppm[start..(start + 3)]
+}
+
+#[test]
+fn ppm_colors() {
+    let mut ppm = Vec::new();
+    heatmap::<DenseGrid>(EXAMPLE).write_ppm(&mut ppm).unwrap();
+
+    assert!(ppm.starts_with(b"P6\n7 7\n255\n"));
+    assert_eq!(ppm.len(), 11 + 7 * 7 * 3);
+
+    let background = pixel(&ppm, 7, 0, 0);
+    let claimed_once = pixel(&ppm, 7, 1, 3);
+    let overlap = pixel(&ppm, 7, 3, 3);
+    let uncontested = pixel(&ppm, 7, 5, 5);
+    assert_ne!(background, claimed_once);
+    assert_ne!(claimed_once, overlap);
+    assert_ne!(overlap, uncontested);
+    assert_eq!(pixel(&ppm, 7, 4, 4), overlap);
+    assert_eq!(pixel(&ppm, 7, 6, 6), uncontested);
+}
+
+#[test]
+fn grids_render_the_same() {
+    let (mut dense, mut map) = (Vec::new(), Vec::new());
+
+    heatmap::<DenseGrid>(EXAMPLE).write_ppm(&mut dense).unwrap();
+    heatmap::<MapGrid>(EXAMPLE).write_ppm(&mut map).unwrap();
+    assert_eq!(dense, map);
+}
+
+#[test]
+fn png_signature() {
+    let mut png = Vec::new();
+    heatmap::<DenseGrid>(EXAMPLE).write_png(&mut png).unwrap();
+
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+fn downscaled_preview() {
+    let heatmap = heatmap::<DenseGrid>("#1 @ 0,0: 100x30\n#2 @ 50,10: 20x20\n");
+
+    let small = heatmap.downscale(7);
+    assert_eq!((small.width(), small.height()), (15, 5));
+
+    /* 100 columns in 20 characters, 30 rows in 3 lines of two pixels */
+    let preview = heatmap.to_ansi(20);
+    let lines: Vec<&str> = preview.lines().collect();
+    assert_eq!(lines.len(), 3);
+    for line in lines {
+        assert_eq!(line.matches('\u{2580}').count(), 20);
+        assert!(line.ends_with("\x1b[0m"));
+    }
+}
@@ -0,0 +1,40 @@
+/* Print the sleep histogram of every guard as a heatmap, the sleepiest guards first.
+ *
+ * usage: report [--color] [--csv <file>] [<file>]
+ *
+ * With --color, the heatmap is drawn for 24-bit color terminals. With --csv, the histograms are
+ * also exported to a CSV file. Without a file, the logs are read from stdin.
+ */
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+
+use aoc18_rust_day4::{Day4, Result, SleepReport};
+use common::{parse_args, read_input, Solution};
+
+fn main() -> Result<()> {
+    let mut color = false;
+    let mut csv = None;
+
+    let path = parse_args(env::args().skip(1), |option, args| {
+        match option {
+            "--color" => color = true,
+            "--csv" => csv = Some(args.next().ok_or("missing CSV file")?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let input = read_input(path.as_deref())?;
+
+    let guards = Day4::default().parse(&input)?;
+    let report = SleepReport::new(&guards);
+
+    if let Some(csv) = csv {
+        let mut out = BufWriter::new(File::create(csv)?);
+        report.write_csv(&mut out)?;
+        out.flush()?;
+    }
+    report.write_heatmap(&mut io::stdout().lock(), color)?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{parse_logs, Log, LogType, Result};
+
+/* Inconsistency in the guard logs */
+#[derive(Debug, PartialEq)]
+pub enum Issue {
+    /* a guard wakes up without having fallen asleep */
+    WakeWithoutSleep,
+    /* a guard falls asleep and the shift ends before the guard wakes up */
+    SleepWithoutWake,
+    /* a guard falls asleep twice without waking up */
+    AlreadyAsleep,
+    /* a guard falls asleep or wakes up before any shift began */
+    BeforeFirstShift,
+    /* an event happens at the same time as the event logged on the given line */
+    DuplicateTimestamp(usize),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::WakeWithoutSleep => write!(f, "wakes up without falling asleep"),
+            Issue::SleepWithoutWake => write!(f, "falls asleep until the end of the shift"),
+            Issue::AlreadyAsleep => write!(f, "falls asleep while already asleep"),
+            Issue::BeforeFirstShift => write!(f, "event before the first shift"),
+            Issue::DuplicateTimestamp(line) => write!(f, "same timestamp as line {}", line),
+        }
+    }
+}
+
+/* Inconsistent log, with its line in the input */
+#[derive(Debug, PartialEq)]
+pub struct LogIssue {
+    pub line: usize,
+    pub text: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for LogIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} in \"{}\"", self.line, self.issue, self.text)
+    }
+}
+
+/* Every inconsistency of the logs, one per line */
+#[derive(Debug)]
+pub struct LogIssues(pub Vec<LogIssue>);
+
+impl fmt::Display for LogIssues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let issues: Vec<String> = self.0.iter().map(|i| i.to_string()).collect();
+        write!(f, "{}", issues.join("\n"))
+    }
+}
+
+impl Error for LogIssues {}
+
+/* Parse the logs, and list their inconsistencies in input order. */
+pub fn check_logs(input: &str) -> Result<Vec<LogIssue>> {
+    let logs = parse_logs(input)?;
+
+    Ok(check_sorted_logs(input, &logs))
+}
+
+pub(crate) fn check_sorted_logs(input: &str, logs: &[Log]) -> Vec<LogIssue> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut issues = Vec::new();
+    let mut report = |log: &Log, issue| {
+        issues.push(LogIssue {
+            line: log.line,
+            text: lines[log.line - 1].to_owned(),
+            issue,
+        })
+    };
+
+    let mut prev: Option<&Log> = None;
+    let mut in_shift = false;
+    let mut asleep: Option<&Log> = None;
+
+    for log in logs {
+        if let Some(prev) = prev.filter(|prev| prev.time == log.time) {
+            report(log, Issue::DuplicateTimestamp(prev.line));
+        }
+        prev = Some(log);
+
+        match log.typ {
+            LogType::BeginShift(_) => {
+                if let Some(sleep) = asleep.take() {
+                    report(sleep, Issue::SleepWithoutWake);
+                }
+                in_shift = true;
+            }
+            _ if !in_shift => report(log, Issue::BeforeFirstShift),
+            LogType::FallsAsleep => {
+                if asleep.is_some() {
+                    report(log, Issue::AlreadyAsleep);
+                } else {
+                    asleep = Some(log);
+                }
+            }
+            LogType::WakesUp => {
+                if asleep.take().is_none() {
+                    report(log, Issue::WakeWithoutSleep);
+                }
+            }
+        }
+    }
+    if let Some(sleep) = asleep {
+        report(sleep, Issue::SleepWithoutWake);
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
@@ -0,0 +1,411 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::iter::Iterator;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use scan_fmt::scan_fmt;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+mod check;
+mod report;
+mod strategy;
+
+pub use check::{check_logs, Issue, LogIssue, LogIssues};
+pub use report::SleepReport;
+pub use strategy::{
+    apply_strategy, LongestNap, MostAsleep, MostConsistent, MostFrequentMinute, MostNights,
+    Strategy, StrategyKind,
+};
+
+pub struct Day4 {
+    /* fail on inconsistent logs, listing every inconsistency, instead of ignoring them */
+    pub check: bool,
+    /* strategies used to choose the guard and minute of each part */
+    pub strategy1: StrategyKind,
+    pub strategy2: StrategyKind,
+}
+
+impl Default for Day4 {
+    fn default() -> Self {
+        Day4 {
+            check: false,
+            strategy1: StrategyKind::MostAsleep,
+            strategy2: StrategyKind::MostFrequentMinute,
+        }
+    }
+}
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = HashMap<u32, GuardSleeping>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<HashMap<u32, GuardSleeping>> {
+        let logs = parse_logs(input)?;
+
+        if self.check {
+            let issues = check::check_sorted_logs(input, &logs);
+            if !issues.is_empty() {
+                return Err(Box::new(LogIssues(issues)));
+            }
+        }
+        Ok(build_guards_map(&logs))
+    }
+
+    fn part1(&self, map: &HashMap<u32, GuardSleeping>) -> Result<u64> {
+        apply_strategy(&self.strategy1, map)
+    }
+
+    fn part2(&self, map: &HashMap<u32, GuardSleeping>) -> Result<u64> {
+        apply_strategy(&self.strategy2, map)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "check" => self.check = value.parse()?,
+            "strategy1" => self.strategy1 = value.parse()?,
+            "strategy2" => self.strategy2 = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 4", name).into()),
+        }
+        Ok(())
+    }
+}
+
+fn parse_logs(input: &str) -> Result<Vec<Log>> {
+    let mut logs: Vec<Log> = parse_lines(input)?;
+
+    for (idx, log) in logs.iter_mut().enumerate() {
+        log.line = idx + 1;
+    }
+    /* sort all logs chronologically, logs with the same timestamp in input order */
+    logs.sort();
+    Ok(logs)
+}
+
+/* {{{ Guard map */
+
+/* minutes in a day, one sleep record each */
+pub const MINUTES_PER_DAY: usize = 24 * 60;
+
+/* Position of a minute of the day from noon, so that the minutes of a night shift, from the
+ * evening to the morning, are in order. It is also the minute of the day at a position.
+ */
+pub(crate) fn shift_position(minute: usize) -> usize {
+    (minute + MINUTES_PER_DAY / 2) % MINUTES_PER_DAY
+}
+
+pub struct GuardSleeping {
+    /* number of times a guard was sleeping, per minute of the day */
+    sleep_records: Vec<u32>,
+    /* total number of minutes sleeping */
+    total_minutes: u32,
+    /* duration of the longest nap, in minutes */
+    longest_nap: u32,
+    /* number of shifts during which the guard slept */
+    nights_asleep: u32,
+}
+
+impl GuardSleeping {
+    fn new() -> Self {
+        GuardSleeping {
+            sleep_records: vec![0; MINUTES_PER_DAY],
+            total_minutes: 0,
+            longest_nap: 0,
+            nights_asleep: 0,
+        }
+    }
+
+    /* number of times the guard was sleeping, per minute of the day, from midnight */
+    pub fn sleep_records(&self) -> &[u32] {
+        &self.sleep_records
+    }
+
+    pub fn total_minutes(&self) -> u32 {
+        self.total_minutes
+    }
+
+    pub fn longest_nap(&self) -> u32 {
+        self.longest_nap
+    }
+
+    pub fn nights_asleep(&self) -> u32 {
+        self.nights_asleep
+    }
+
+    /* Minute of the day most often spent asleep, the earliest on ties, and the number of times.
+     * For naps during the midnight hour, this is the minute after midnight.
+     */
+    pub fn sleepiest_minute(&self) -> (usize, u32) {
+        self.sleep_records
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|&(minute, count)| (count, cmp::Reverse(minute)))
+            .unwrap()
+    }
+
+    /* Record a nap, from its first minute asleep to the minute of waking up. Naps can span
+     * midnight or several hours, and even several days.
+     */
+    fn add_sleepy_time(&mut self, start: &DateTime, end: &DateTime) {
+        let (start, end) = (start.minute_number(), end.minute_number());
+        let duration = (end - start) as u32;
+
+        self.total_minutes += duration;
+        self.longest_nap = cmp::max(self.longest_nap, duration);
+        for m in start..end {
+            self.sleep_records[m.rem_euclid(MINUTES_PER_DAY as i64) as usize] += 1;
+        }
+    }
+}
+
+/* Inconsistent logs, listed by check_logs, are ignored: events before the first shift, naps
+ * not ended before the end of the shift, and wakes without naps. When falling asleep twice, the
+ * nap starts on the first time.
+ */
+fn build_guards_map(logs: &[Log]) -> HashMap<u32, GuardSleeping> {
+    let mut map = HashMap::new();
+    let mut cur_guard_id = None;
+    let mut asleep_time = None;
+    /* whether the current guard already slept during the shift */
+    let mut slept = false;
+
+    for log in logs {
+        match log.typ {
+            LogType::BeginShift(guard_id) => {
+                map.entry(guard_id).or_insert_with(GuardSleeping::new);
+                cur_guard_id = Some(guard_id);
+                asleep_time = None;
+                slept = false;
+            }
+            LogType::FallsAsleep => {
+                if asleep_time.is_none() {
+                    asleep_time = Some(log.time);
+                }
+            }
+            LogType::WakesUp => {
+                if let (Some(guard_id), Some(start)) = (cur_guard_id, asleep_time.take()) {
+                    if start < log.time {
+                        let guard = map.get_mut(&guard_id).unwrap();
+                        guard.add_sleepy_time(&start, &log.time);
+                        if !slept {
+                            guard.nights_asleep += 1;
+                            slept = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    map
+}
+
+/* }}} */
+/* {{{ Date */
+
+/* Day of the proleptic Gregorian calendar */
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Debug)]
+pub struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /* None if the day does not exist */
+    pub fn new(year: u32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn next_day(&self) -> Date {
+        if self.day < days_in_month(self.year, self.month) {
+            Date {
+                day: self.day + 1,
+                ..*self
+            }
+        } else if self.month < 12 {
+            Date {
+                year: self.year,
+                month: self.month + 1,
+                day: 1,
+            }
+        } else {
+            Date {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
+
+    /* Number of days since 0000-03-01, years starting in March so that leap days come last. */
+    pub fn day_number(&self) -> i64 {
+        let (year, month) = if self.month <= 2 {
+            (i64::from(self.year) - 1, i64::from(self.month) + 9)
+        } else {
+            (i64::from(self.year), i64::from(self.month) - 3)
+        };
+
+        let leap_days = year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400);
+        year * 365 + leap_days + (153 * month + 2) / 5 + i64::from(self.day) - 1
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/* Minute of a day */
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Debug)]
+pub struct DateTime {
+    date: Date,
+    hour: u8,
+    minute: u8,
+}
+
+impl DateTime {
+    /* None if the date or the time does not exist */
+    pub fn new(date: Date, hour: u8, minute: u8) -> Option<Self> {
+        if hour >= 24 || minute >= 60 {
+            return None;
+        }
+        Some(DateTime { date, hour, minute })
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /* Number of minutes since 0000-03-01 00:00, to compute durations. */
+    pub fn minute_number(&self) -> i64 {
+        self.date.day_number() * 24 * 60 + i64::from(self.hour) * 60 + i64::from(self.minute)
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.date, self.hour, self.minute)
+    }
+}
+
+/* }}} */
+/* {{{ Log */
+
+#[derive(Debug)]
+enum LogType {
+    BeginShift(u32),
+    FallsAsleep,
+    WakesUp,
+}
+
+#[derive(Debug)]
+struct Log {
+    time: DateTime,
+    typ: LogType,
+    /* line of the log in the input, starting at 1 */
+    line: usize,
+}
+
+/* {{{ Ordering */
+
+impl PartialOrd for Log {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Log {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.time.cmp(&other.time)
+    }
+}
+
+impl PartialEq for Log {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time
+    }
+}
+impl Eq for Log {}
+
+/* }}} */
+/* {{{ FromStr */
+
+const LOG_PATTERN: &str = "[{d}-{d}-{d} {d}:{d}] {[a-zA-Z#0-9 ]}";
+const SHIFT_PATTERN: &str = "Guard #{d} begins shift";
+
+impl FromStr for Log {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (year, month, day, h, m, text) =
+            scan_fmt!(s, LOG_PATTERN, u32, u32, u32, u8, u8, String);
+        let year = field(year, "year", s, LOG_PATTERN)?;
+        let month = field(month, "month", s, LOG_PATTERN)?;
+        let day = field(day, "day", s, LOG_PATTERN)?;
+        let hour = field(h, "hour", s, LOG_PATTERN)?;
+        let minute = field(m, "minute", s, LOG_PATTERN)?;
+        let text = field(text, "event", s, LOG_PATTERN)?;
+
+        let date = Date::new(year, month, day)
+            .ok_or_else(|| ParseError::new(s, LOG_PATTERN).with_field("date"))?;
+        let time = DateTime::new(date, hour, minute)
+            .ok_or_else(|| ParseError::new(s, LOG_PATTERN).with_field("time"))?;
+
+        let typ = match text.as_ref() {
+            "falls asleep" => LogType::FallsAsleep,
+            "wakes up" => LogType::WakesUp,
+            _ => {
+                let id = scan_fmt!(&text, SHIFT_PATTERN, u32);
+                LogType::BeginShift(field(id, "guard id", s, SHIFT_PATTERN)?)
+            }
+        };
+        Ok(Log { time, typ, line: 0 })
+    }
+}
+
+/* }}} */
+/* }}} */
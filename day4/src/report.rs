@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+use std::ops::Range;
+
+use crate::{shift_position, GuardSleeping};
+
+/* shades of the ASCII heatmap, from never asleep to asleep on the most nights */
+const SHADES: &[u8] = b" .:-=+*#%@";
+/* most columns of the heatmap, minutes being grouped when the guards slept over longer spans */
+const MAX_COLUMNS: usize = 60;
+
+/* Sleep histogram of every guard, the sleepiest guards first. */
+pub struct SleepReport<'a> {
+    guards: Vec<(u32, &'a GuardSleeping)>,
+    /* largest number of nights spent asleep on the same minute, by any guard */
+    max_count: u32,
+    /* positions from noon of the minutes reported: the midnight hour, extended to every minute
+     * a guard slept
+     */
+    span: Range<usize>,
+}
+
+impl<'a> SleepReport<'a> {
+    pub fn new(map: &'a HashMap<u32, GuardSleeping>) -> Self {
+        let mut guards: Vec<(u32, &GuardSleeping)> = map.iter().map(|(&id, g)| (id, g)).collect();
+        guards.sort_unstable_by_key(|&(id, guard)| (std::cmp::Reverse(guard.total_minutes), id));
+
+        let max_count = guards
+            .iter()
+            .flat_map(|(_, guard)| guard.sleep_records.iter())
+            .copied()
+            .max()
+            .unwrap_or(0);
+
+        let midnight = shift_position(0);
+        let mut span = midnight..midnight + 60;
+        for (_, guard) in &guards {
+            for (minute, _) in guard
+                .sleep_records
+                .iter()
+                .enumerate()
+                .filter(|(_, &c)| c > 0)
+            {
+                let position = shift_position(minute);
+                span.start = span.start.min(position);
+                span.end = span.end.max(position + 1);
+            }
+        }
+
+        SleepReport {
+            guards,
+            max_count,
+            span,
+        }
+    }
+
+    /* Minutes reported in each column of the heatmap, so that there are at most 60 columns. */
+    fn column_width(&self) -> usize {
+        self.span.len().div_ceil(MAX_COLUMNS)
+    }
+
+    /* positions from noon of the minutes of each column of the heatmap */
+    fn columns(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let width = self.column_width();
+        let end = self.span.end;
+
+        self.span
+            .clone()
+            .step_by(width)
+            .map(move |start| start..(start + width).min(end))
+    }
+
+    /* ids of the guards, in report order */
+    pub fn guards(&self) -> impl Iterator<Item = u32> + '_ {
+        self.guards.iter().map(|&(id, _)| id)
+    }
+
+    /* Shade, from 0 to 9, of a number of nights asleep on a minute. Any sleep is visible. */
+    fn shade(&self, count: u32) -> usize {
+        if count == 0 {
+            return 0;
+        }
+        1 + ((count - 1) as usize * (SHADES.len() - 2)) / (self.max_count as usize - 1).max(1)
+    }
+
+    /* One row per guard and one column per minute, or group of minutes for naps over longer
+     * spans than the midnight hour, darker when the guard was asleep on more nights. With color,
+     * the columns are drawn with 24-bit ANSI colors instead.
+     */
+    pub fn write_heatmap<W: Write>(&self, out: &mut W, color: bool) -> io::Result<()> {
+        /* minute in the hour of the first minute of each column */
+        let minutes: Vec<usize> = self
+            .columns()
+            .map(|c| shift_position(c.start) % 60)
+            .collect();
+        let tens: String = minutes
+            .iter()
+            .map(|m| (b'0' + (m / 10) as u8) as char)
+            .collect();
+        let units: String = minutes
+            .iter()
+            .map(|m| (b'0' + (m % 10) as u8) as char)
+            .collect();
+        writeln!(out, "{:>6} {:>5} {}", "", "", tens)?;
+        writeln!(out, "{:>6} {:>5} {}", "guard", "total", units)?;
+
+        for (id, guard) in &self.guards {
+            write!(out, "{:>6} {:>5} ", format!("#{}", id), guard.total_minutes)?;
+            for column in self.columns() {
+                let count = column
+                    .map(|position| guard.sleep_records[shift_position(position)])
+                    .max()
+                    .unwrap_or(0);
+                let shade = self.shade(count);
+                if color {
+                    /* from dark blue to bright red */
+                    let ratio = shade as u32 * 255 / (SHADES.len() as u32 - 1);
+                    write!(out, "\x1b[48;2;{};20;{}m ", ratio, 80 - ratio * 80 / 255)?;
+                } else {
+                    write!(out, "{}", SHADES[shade] as char)?;
+                }
+            }
+            if color {
+                write!(out, "\x1b[0m")?;
+            }
+            writeln!(out)?;
+        }
+        writeln!(out, "most nights asleep on a minute: {}", self.max_count)?;
+
+        let last = shift_position(self.span.end - 1);
+        let first = shift_position(self.span.start);
+        let width = self.column_width();
+        writeln!(
+            out,
+            "columns from {:02}:{:02} to {:02}:{:02}, {} minute{} each",
+            first / 60,
+            first % 60,
+            last / 60,
+            last % 60,
+            width,
+            if width > 1 { "s" } else { "" }
+        )
+    }
+
+    /* One row per guard: the guard id, total minutes asleep, and nights asleep per minute of the
+     * heatmap, minutes being labelled by their number from midnight.
+     */
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let minutes: Vec<String> = self
+            .span
+            .clone()
+            .map(|p| shift_position(p).to_string())
+            .collect();
+        writeln!(out, "guard,total,{}", minutes.join(","))?;
+
+        for (id, guard) in &self.guards {
+            let counts: Vec<String> = self
+                .span
+                .clone()
+                .map(|p| guard.sleep_records[shift_position(p)].to_string())
+                .collect();
+            writeln!(out, "{},{},{}", id, guard.total_minutes, counts.join(","))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::{shift_position, GuardSleeping, Result};
+
+/* Way of choosing the guard to sneak past, and the minute to do it. */
+pub trait Strategy {
+    /* Chosen guard id and minute, None if no guard was ever asleep. */
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)>;
+}
+
+/* Puzzle answer for a strategy: the chosen guard id multiplied by the chosen minute of the day. */
+pub fn apply_strategy<S: Strategy>(
+    strategy: &S,
+    guards: &HashMap<u32, GuardSleeping>,
+) -> Result<u64> {
+    let (guard_id, minute) = strategy.choose(guards).ok_or("no guard was ever asleep")?;
+
+    Ok(u64::from(guard_id) * minute as u64)
+}
+
+/* Guard with the greatest key among the guards that slept, the lowest id on ties, and its
+ * sleepiest minute.
+ */
+fn choose_guard<K, F>(guards: &HashMap<u32, GuardSleeping>, key: F) -> Option<(u32, usize)>
+where
+    K: Ord,
+    F: Fn(&GuardSleeping) -> K,
+{
+    let (guard_id, guard) = guards
+        .iter()
+        .filter(|(_, guard)| guard.total_minutes > 0)
+        .max_by_key(|&(&id, guard)| (key(guard), Reverse(id)))?;
+
+    Some((*guard_id, guard.sleepiest_minute().0))
+}
+
+/* {{{ Strategies */
+
+/* The guard with the most minutes asleep, on its sleepiest minute: strategy 1 of the puzzle. */
+pub struct MostAsleep;
+
+impl Strategy for MostAsleep {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        choose_guard(guards, |guard| guard.total_minutes)
+    }
+}
+
+/* The guard most frequently asleep on the same minute: strategy 2 of the puzzle. */
+pub struct MostFrequentMinute;
+
+impl Strategy for MostFrequentMinute {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        choose_guard(guards, |guard| guard.sleepiest_minute().1)
+    }
+}
+
+/* The guard whose minutes asleep vary the least, on its sleepiest minute. */
+pub struct MostConsistent;
+
+impl MostConsistent {
+    /* Variance of the minutes asleep, for a guard that slept. Minutes are taken from noon, so
+     * that naps around midnight are not spread over both ends of the day.
+     */
+    fn variance(guard: &GuardSleeping) -> f64 {
+        let total = f64::from(guard.total_minutes);
+        let weighted = guard
+            .sleep_records
+            .iter()
+            .enumerate()
+            .map(|(m, count)| (shift_position(m), count));
+
+        let mean = weighted
+            .clone()
+            .map(|(m, &count)| m as f64 * f64::from(count))
+            .sum::<f64>()
+            / total;
+        weighted
+            .map(|(m, &count)| (m as f64 - mean).powi(2) * f64::from(count))
+            .sum::<f64>()
+            / total
+    }
+}
+
+impl Strategy for MostConsistent {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        let (guard_id, guard) = guards
+            .iter()
+            .filter(|(_, guard)| guard.total_minutes > 0)
+            .map(|(&id, guard)| (id, guard, Self::variance(guard)))
+            .min_by(|a, b| a.2.total_cmp(&b.2).then(a.0.cmp(&b.0)))
+            .map(|(id, guard, _)| (id, guard))?;
+
+        Some((guard_id, guard.sleepiest_minute().0))
+    }
+}
+
+/* The guard that took the longest nap, on its sleepiest minute. */
+pub struct LongestNap;
+
+impl Strategy for LongestNap {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        choose_guard(guards, |guard| guard.longest_nap)
+    }
+}
+
+/* The guard that slept during the most shifts, on its sleepiest minute. */
+pub struct MostNights;
+
+impl Strategy for MostNights {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        choose_guard(guards, |guard| guard.nights_asleep)
+    }
+}
+
+/* }}} */
+/* {{{ StrategyKind */
+
+/* Strategy selected at runtime */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrategyKind {
+    MostAsleep,
+    MostFrequentMinute,
+    MostConsistent,
+    LongestNap,
+    MostNights,
+}
+
+impl FromStr for StrategyKind {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "most-asleep" => Ok(StrategyKind::MostAsleep),
+            "most-frequent-minute" => Ok(StrategyKind::MostFrequentMinute),
+            "most-consistent" => Ok(StrategyKind::MostConsistent),
+            "longest-nap" => Ok(StrategyKind::LongestNap),
+            "most-nights" => Ok(StrategyKind::MostNights),
+            _ => Err(format!(
+                "unknown strategy {}, expected most-asleep, most-frequent-minute, \
+                 most-consistent, longest-nap or most-nights",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+impl Strategy for StrategyKind {
+    fn choose(&self, guards: &HashMap<u32, GuardSleeping>) -> Option<(u32, usize)> {
+        match self {
+            StrategyKind::MostAsleep => MostAsleep.choose(guards),
+            StrategyKind::MostFrequentMinute => MostFrequentMinute.choose(guards),
+            StrategyKind::MostConsistent => MostConsistent.choose(guards),
+            StrategyKind::LongestNap => LongestNap.choose(guards),
+            StrategyKind::MostNights => MostNights.choose(guards),
+        }
+    }
+}
+
+/* }}} */
@@ -0,0 +1,62 @@
+use std::fs;
+
+use aoc18_rust_day4::{check_logs, Day4, Issue};
+use common::Solution;
+
+#[test]
+fn committed_input_is_consistent() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+
+    assert!(check_logs(&input).unwrap().is_empty());
+}
+
+#[test]
+fn every_issue() {
+    let input = "\
+[1518-10-31 00:10] wakes up
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:07] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] wakes up
+[1518-11-01 00:40] falls asleep
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:40] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+";
+    let issues: Vec<(usize, Issue)> = check_logs(input)
+        .unwrap()
+        .into_iter()
+        .map(|i| (i.line, i.issue))
+        .collect();
+
+    assert_eq!(
+        issues,
+        vec![
+            (1, Issue::BeforeFirstShift),
+            (4, Issue::AlreadyAsleep),
+            (6, Issue::WakeWithoutSleep),
+            (7, Issue::SleepWithoutWake),
+            (10, Issue::DuplicateTimestamp(9)),
+            (12, Issue::SleepWithoutWake),
+        ]
+    );
+}
+
+#[test]
+fn check_mode() {
+    let input = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up\n";
+    let mut day4 = Day4::default();
+
+    /* inconsistent logs are ignored by default */
+    assert!(day4.parse(input).is_ok());
+
+    day4.set_param("check", "true").unwrap();
+    let err = day4.parse(input).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "line 2: wakes up without falling asleep in \"[1518-11-01 00:25] wakes up\""
+    );
+}
@@ -0,0 +1,59 @@
+use aoc18_rust_day4::{Date, DateTime};
+
+fn date(year: u32, month: u32, day: u32) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+#[test]
+fn invalid_dates() {
+    assert!(Date::new(1518, 0, 1).is_none());
+    assert!(Date::new(1518, 13, 1).is_none());
+    assert!(Date::new(1518, 4, 31).is_none());
+    assert!(Date::new(1518, 2, 29).is_none());
+    assert!(Date::new(1520, 2, 29).is_some());
+    assert!(Date::new(1600, 2, 29).is_some());
+    assert!(Date::new(1700, 2, 29).is_none());
+    assert!(DateTime::new(date(1518, 1, 1), 24, 0).is_none());
+    assert!(DateTime::new(date(1518, 1, 1), 23, 60).is_none());
+}
+
+#[test]
+fn next_day() {
+    assert_eq!(date(1518, 3, 30).next_day(), date(1518, 3, 31));
+    assert_eq!(date(1518, 3, 31).next_day(), date(1518, 4, 1));
+    assert_eq!(date(1518, 2, 28).next_day(), date(1518, 3, 1));
+    assert_eq!(date(1520, 2, 28).next_day(), date(1520, 2, 29));
+    assert_eq!(date(1518, 12, 31).next_day(), date(1519, 1, 1));
+}
+
+#[test]
+fn day_numbers() {
+    assert_eq!(date(0, 3, 1).day_number(), 0);
+    assert_eq!(
+        date(1519, 1, 1).day_number() - date(1518, 1, 1).day_number(),
+        365
+    );
+    assert_eq!(
+        date(1521, 1, 1).day_number() - date(1520, 1, 1).day_number(),
+        366
+    );
+
+    let mut day = date(1500, 1, 1);
+    for _ in 0..100_000 {
+        assert_eq!(day.next_day().day_number(), day.day_number() + 1);
+        day = day.next_day();
+    }
+}
+
+#[test]
+fn times_across_midnight() {
+    let before_midnight = DateTime::new(date(1518, 12, 31), 23, 58).unwrap();
+    let after_midnight = DateTime::new(date(1519, 1, 1), 0, 2).unwrap();
+
+    assert!(before_midnight < after_midnight);
+    assert_eq!(
+        after_midnight.minute_number() - before_midnight.minute_number(),
+        4
+    );
+    assert_eq!(before_midnight.to_string(), "1518-12-31 23:58");
+}
@@ -0,0 +1,118 @@
+use aoc18_rust_day4::{Day4, SleepReport};
+use common::Solution;
+
+const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+#[test]
+fn heatmap() {
+    let guards = Day4::default().parse(EXAMPLE).unwrap();
+    let report = SleepReport::new(&guards);
+    assert_eq!(report.guards().collect::<Vec<_>>(), vec![10, 99]);
+
+    let mut out = Vec::new();
+    report.write_heatmap(&mut out, false).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+
+    assert_eq!(lines.len(), 6);
+    assert_eq!(
+        lines[2],
+        "   #10    50      ...................+.... .........................     "
+    );
+    assert_eq!(
+        lines[3],
+        "   #99    30                                     ....+++++@++++.....     "
+    );
+    assert_eq!(lines[4], "most nights asleep on a minute: 3");
+    assert_eq!(lines[5], "columns from 00:00 to 00:59, 1 minute each");
+
+    let mut out = Vec::new();
+    report.write_heatmap(&mut out, true).unwrap();
+    assert!(String::from_utf8(out).unwrap().contains("\x1b[48;2;"));
+}
+
+#[test]
+fn csv() {
+    let guards = Day4::default().parse(EXAMPLE).unwrap();
+    let mut out = Vec::new();
+    SleepReport::new(&guards).write_csv(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("guard,total,0,1,2,"));
+    assert!(lines[0].ends_with(",58,59"));
+    assert!(lines[1].starts_with("10,50,0,0,0,0,0,1,"));
+    assert_eq!(lines[2].split(',').nth(2 + 45), Some("3"));
+}
+
+const OVERNIGHT: &str = "\
+[1518-11-01 22:00] Guard #10 begins shift
+[1518-11-01 23:30] falls asleep
+[1518-11-02 00:30] wakes up
+[1518-11-02 22:00] Guard #99 begins shift
+[1518-11-03 01:00] falls asleep
+[1518-11-03 03:00] wakes up
+";
+
+#[test]
+fn overnight_heatmap() {
+    let guards = Day4::default().parse(OVERNIGHT).unwrap();
+    assert_eq!(guards[&10].total_minutes(), 60);
+    assert_eq!(guards[&99].longest_nap(), 120);
+
+    let mut out = Vec::new();
+    SleepReport::new(&guards)
+        .write_heatmap(&mut out, false)
+        .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+
+    /* from 23:30 to 02:59, 210 minutes in 53 columns of 4 minutes */
+    assert_eq!(
+        lines[0].trim(),
+        "33344555001112233344555001112233344555001112233344555"
+    );
+    /* the nap from 01:00 starts within the column from 00:58 */
+    assert_eq!(
+        lines[2],
+        format!("   #99   120 {}{}", " ".repeat(22), ".".repeat(31))
+    );
+    assert_eq!(
+        lines[3],
+        format!("   #10    60 {}{}", ".".repeat(15), " ".repeat(38))
+    );
+    assert_eq!(lines[5], "columns from 23:30 to 02:59, 4 minutes each");
+}
+
+#[test]
+fn overnight_csv() {
+    let guards = Day4::default().parse(OVERNIGHT).unwrap();
+    let mut out = Vec::new();
+    SleepReport::new(&guards).write_csv(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+
+    assert!(lines[0].starts_with("guard,total,1410,1411,"));
+    assert!(lines[0].contains(",1439,0,1,"));
+    assert!(lines[0].ends_with(",178,179"));
+    assert!(lines[2].starts_with("10,60,1,1,"));
+}
@@ -0,0 +1,76 @@
+/* Fully react a polymer of any size, without loading it in memory.
+ *
+ * usage: react [<options>] [<file>]
+ *        react [<options>] --generate <nb units> [--seed <seed>] [--types <nb unit types>]
+ *
+ * options: --rule <ascii|unicode>  units reacting, same letters of opposite cases by default
+ *          --pairs <file>          react the pairs of units listed in a file instead
+ *          --trace <file|->        write every reaction to a file, or stdout, and print the
+ *                                  unit types that reacted most
+ *
+ * Without a file, the polymer is read from stdin.
+ */
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Read, Write};
+
+use aoc18_rust_day5::{
+    parse_rule, react_stream, react_stream_traced, PairTable, PolymerGenerator, ReactionStats,
+    Result, Rule,
+};
+use common::parse_args;
+
+fn main() -> Result<()> {
+    let mut rule = Rule::default();
+    let mut trace = None;
+    let mut generate = None;
+    let mut seed = 1;
+    let mut nb_types = 26;
+
+    let path = parse_args(env::args().skip(1), |option, args| {
+        let mut value = || args.next().ok_or(format!("missing value for {}", option));
+        match option {
+            "--rule" => rule = parse_rule(&value()?)?,
+            "--pairs" => rule = Rule::Table(PairTable::load(&value()?)?),
+            "--trace" => trace = Some(value()?),
+            "--generate" => generate = Some(value()?.parse()?),
+            "--seed" => seed = value()?.parse()?,
+            "--types" => nb_types = value()?.parse()?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    let reader: Box<dyn Read> = match (generate, path) {
+        (None, None) => Box::new(io::stdin().lock()),
+        (Some(nb_units), None) => Box::new(PolymerGenerator::new(nb_units, seed, nb_types)),
+        (None, Some(path)) => Box::new(File::open(path)?),
+        (Some(_), Some(_)) => return Err("cannot both generate a polymer and read a file".into()),
+    };
+
+    match trace {
+        None => {
+            let len = react_stream(reader, rule)?;
+            println!("reduced polymer length: {}", len);
+        }
+        Some(path) => {
+            let mut out: BufWriter<Box<dyn Write>> = BufWriter::new(match path.as_str() {
+                "-" => Box::new(io::stdout()),
+                _ => Box::new(File::create(&path)?),
+            });
+            let mut stats = ReactionStats::default();
+
+            writeln!(out, "# left-position left right-position right depth")?;
+            let len = react_stream_traced(reader, rule, |reaction| {
+                stats.record(reaction);
+                writeln!(out, "{}", reaction)
+            })?;
+            out.flush()?;
+
+            println!("reduced polymer length: {}", len);
+            println!("{}", stats);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::str;
+use std::thread;
+
+use common::{Solution, XorShift};
+
+mod rules;
+pub use rules::{AsciiCase, PairTable, ReactionRule, Rule, UnicodeCase};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Default)]
+pub struct Day5 {
+    /* display the reduced length without every unit type in part2, not only the shortest */
+    pub table: bool,
+    /* which adjacent units react */
+    pub rule: Rule,
+}
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = UnitLengths;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        /* ignore extra newline */
+        Ok(input.trim_end().to_owned())
+    }
+
+    fn part1(&self, polymer: &String) -> Result<usize> {
+        Ok(react(polymer, &self.rule))
+    }
+
+    fn part2(&self, polymer: &String) -> Result<UnitLengths> {
+        let lengths = lengths_without_each_unit(polymer, &self.rule);
+
+        if lengths.is_empty() {
+            return Err(Box::from("empty polymer"));
+        }
+        Ok(UnitLengths {
+            lengths,
+            table: self.table,
+        })
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "table" => self.table = value.parse()?,
+            "rule" => self.rule = parse_rule(value)?,
+            "pairs" => self.rule = Rule::Table(PairTable::load(value)?),
+            _ => return Err(format!("unknown parameter {} for day 5", name).into()),
+        }
+        Ok(())
+    }
+}
+
+/* Case rule from its name, pair tables being loaded from a file. */
+pub fn parse_rule(name: &str) -> Result<Rule> {
+    match name {
+        "ascii" => Ok(Rule::AsciiCase),
+        "unicode" => Ok(Rule::UnicodeCase),
+        _ => Err(format!("unknown reaction rule {}, expected ascii or unicode", name).into()),
+    }
+}
+
+/* {{{ Reactor */
+
+fn react<R: ReactionRule>(input: &str, rule: R) -> usize {
+    let mut reactor = Reactor::new(rule);
+    reactor.feed(input);
+    reactor.len()
+}
+
+/* Fully react a UTF-8 polymer read in chunks, and return the reduced polymer length. Whitespace
+ * is ignored, so only the reduced polymer is kept in memory, whatever the size of the input.
+ */
+pub fn react_stream<Rd: Read, R: ReactionRule>(reader: Rd, rule: R) -> io::Result<usize> {
+    feed_stream(reader, Reactor::new(rule), |_| Ok(()))
+}
+
+/* Same as react_stream, calling on_reaction on every reaction, in order. The position of every
+ * unit left is kept in memory as well.
+ */
+pub fn react_stream_traced<Rd, R, F>(reader: Rd, rule: R, on_reaction: F) -> io::Result<usize>
+where
+    Rd: Read,
+    R: ReactionRule,
+    F: FnMut(&Reaction) -> io::Result<()>,
+{
+    feed_stream(reader, Reactor::traced(rule), on_reaction)
+}
+
+fn feed_stream<Rd, R, F>(
+    mut reader: Rd,
+    mut reactor: Reactor<R>,
+    mut on_reaction: F,
+) -> io::Result<usize>
+where
+    Rd: Read,
+    R: ReactionRule,
+    F: FnMut(&Reaction) -> io::Result<()>,
+{
+    let mut chunk = vec![0; 64 * 1024];
+    /* bytes of a character split between two chunks, kept at the start of the buffer */
+    let mut pending = 0;
+
+    loop {
+        let n = match reader.read(&mut chunk[pending..]) {
+            Ok(0) if pending > 0 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "truncated UTF-8 character at end of polymer",
+                ))
+            }
+            Ok(0) => return Ok(reactor.len()),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let end = pending + n;
+
+        let valid = match str::from_utf8(&chunk[..end]) {
+            Ok(units) => units.len(),
+            /* incomplete character at the end of the chunk */
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        /* cannot fail, valid is the length of the valid prefix */
+        reactor.feed_traced(str::from_utf8(&chunk[..valid]).unwrap(), &mut on_reaction)?;
+
+        chunk.copy_within(valid..end, 0);
+        pending = end - valid;
+    }
+}
+
+/* Two units that annihilated. Positions are the indexes of the characters in the original
+ * input, whitespace included.
+ */
+pub struct Reaction {
+    pub left: (u64, char),
+    pub right: (u64, char),
+    /* number of units left in the reactor after the reaction */
+    pub depth: usize,
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.left.0, self.left.1, self.right.0, self.right.1, self.depth
+        )
+    }
+}
+
+/* Stack of the units that did not react yet. Units are fed one after the other, and annihilate
+ * with the top of the stack when the reaction rule says so.
+ */
+pub struct Reactor<R: ReactionRule> {
+    rule: R,
+    seq: String,
+    /* number of units in seq */
+    len: usize,
+    /* position in the input of each unit of seq, only kept when tracing */
+    positions: Option<Vec<u64>>,
+    /* number of characters fed so far */
+    offset: u64,
+}
+
+impl<R: ReactionRule> Reactor<R> {
+    pub fn new(rule: R) -> Self {
+        Reactor {
+            rule,
+            seq: String::new(),
+            len: 0,
+            positions: None,
+            offset: 0,
+        }
+    }
+
+    /* Reactor keeping the position of every unit, to report reactions to feed_traced */
+    pub fn traced(rule: R) -> Self {
+        Reactor {
+            positions: Some(Vec::new()),
+            ..Reactor::new(rule)
+        }
+    }
+
+    pub fn feed(&mut self, units: &str) {
+        /* cannot fail, the callback never does */
+        self.feed_traced(units, |_| Ok(())).unwrap();
+    }
+
+    /* Feed units, calling on_reaction on every reaction if the reactor is traced. Stops on the
+     * first error it returns.
+     */
+    pub fn feed_traced<F>(&mut self, units: &str, mut on_reaction: F) -> io::Result<()>
+    where
+        F: FnMut(&Reaction) -> io::Result<()>,
+    {
+        for c in units.chars() {
+            let pos = self.offset;
+            self.offset += 1;
+
+            if c.is_whitespace() {
+                continue;
+            }
+            if let Some(prevc) = self.seq.chars().next_back() {
+                if self.rule.reacts(prevc, c) {
+                    self.seq.pop();
+                    self.len -= 1;
+                    if let Some(positions) = &mut self.positions {
+                        /* cannot fail, seq was not empty */
+                        let prev_pos = positions.pop().unwrap();
+                        on_reaction(&Reaction {
+                            left: (prev_pos, prevc),
+                            right: (pos, c),
+                            depth: self.len,
+                        })?;
+                    }
+                    continue;
+                }
+            }
+            self.seq.push(c);
+            self.len += 1;
+            if let Some(positions) = &mut self.positions {
+                positions.push(pos);
+            }
+        }
+        Ok(())
+    }
+
+    /* the reduced polymer */
+    pub fn polymer(&self) -> &str {
+        &self.seq
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/* Number of reactions per unit type, counted on the second unit of each reaction */
+#[derive(Default)]
+pub struct ReactionStats {
+    pub total: u64,
+    pub by_type: HashMap<char, u64>,
+}
+
+impl ReactionStats {
+    pub fn record(&mut self, reaction: &Reaction) {
+        self.total += 1;
+        *self.by_type.entry(unit_type(reaction.right.1)).or_insert(0) += 1;
+    }
+}
+
+/* The total, then the unit types that reacted most first */
+impl fmt::Display for ReactionStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut counts: Vec<(char, u64)> = self.by_type.iter().map(|(&u, &n)| (u, n)).collect();
+        counts.sort_unstable_by_key(|&(unit, n)| (std::cmp::Reverse(n), unit));
+
+        write!(f, "reactions: {}", self.total)?;
+        for (unit, n) in counts {
+            write!(f, "\n{}: {}", unit, n)?;
+        }
+        Ok(())
+    }
+}
+
+/* }}} */
+/* {{{ Unit removal */
+
+/* Type of a unit: the unit in lowercase. Units without a single-character lowercase form are
+ * their own type.
+ */
+pub fn unit_type(unit: char) -> char {
+    let mut lower = unit.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(c), None) => c,
+        _ => unit,
+    }
+}
+
+/* Reduced length of the polymer without each of its unit types, sorted by length. */
+pub fn lengths_without_each_unit<R>(polymer: &str, rule: &R) -> Vec<(char, usize)>
+where
+    R: ReactionRule + Sync,
+{
+    let mut units: Vec<char> = polymer
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(unit_type)
+        .collect();
+    units.sort_unstable();
+    units.dedup();
+
+    /* When the result of the reactions does not depend on their order, reacting the polymer once
+     * before removing a unit type gives the same result, on a much shorter polymer.
+     */
+    let mut reactor = Reactor::new(rule);
+    let reacted = if rule.confluent() {
+        reactor.feed(polymer);
+        reactor.polymer()
+    } else {
+        polymer
+    };
+
+    /* evaluate the unit types in parallel */
+    let nb_threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = std::cmp::max(1, units.len().div_ceil(nb_threads));
+
+    let mut lengths: Vec<(char, usize)> = thread::scope(|s| {
+        let handles: Vec<_> = units
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&unit| (unit, react(&polymer_without_unit(reacted, unit), rule)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+
+    lengths.sort_unstable_by_key(|&(unit, len)| (len, unit));
+    lengths
+}
+
+fn polymer_without_unit(input: &str, unit: char) -> String {
+    input.chars().filter(|&c| unit_type(c) != unit).collect()
+}
+
+/* Reduced length of the polymer without each unit type, sorted by length */
+pub struct UnitLengths {
+    pub lengths: Vec<(char, usize)>,
+    /* display every length, and not only the shortest */
+    pub table: bool,
+}
+
+impl UnitLengths {
+    pub fn shortest(&self) -> usize {
+        self.lengths[0].1
+    }
+}
+
+impl fmt::Display for UnitLengths {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.table {
+            return write!(f, "{}", self.shortest());
+        }
+
+        let rows: Vec<String> = self
+            .lengths
+            .iter()
+            .map(|&(unit, len)| format!("{}: {}", unit, len))
+            .collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+/* }}} */
+/* {{{ Generator */
+
+/* Pseudo-random polymer of a given length, generated on the fly, to feed the reactor with
+ * arbitrarily large inputs. The same seed always generates the same polymer.
+ */
+pub struct PolymerGenerator {
+    remaining: u64,
+    rng: XorShift,
+    /* number of unit types, starting from 'a' */
+    nb_types: u8,
+}
+
+impl PolymerGenerator {
+    pub fn new(len: u64, seed: u64, nb_types: u8) -> Self {
+        PolymerGenerator {
+            remaining: len,
+            rng: XorShift::new(seed),
+            nb_types: nb_types.clamp(1, 26),
+        }
+    }
+
+    fn next_unit(&mut self) -> u8 {
+        let random = self.rng.next_u64();
+
+        let unit = b'a' + (random % self.nb_types as u64) as u8;
+        if (random >> 32) & 1 == 0 {
+            unit.to_ascii_uppercase()
+        } else {
+            unit
+        }
+    }
+}
+
+impl Read for PolymerGenerator {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = std::cmp::min(buf.len() as u64, self.remaining) as usize;
+
+        for b in &mut buf[..n] {
+            *b = self.next_unit();
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/* }}} */
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::str::FromStr;
+
+use common::{parse_lines, ParseError};
+
+/* Decide whether two adjacent units annihilate. */
+pub trait ReactionRule {
+    /* prev is the unit just before next in the polymer */
+    fn reacts(&self, prev: char, next: char) -> bool;
+
+    /* Whether the reduced length does not depend on the order of the reactions, a unit reacting
+     * with the same units on either side. False unless known.
+     */
+    fn confluent(&self) -> bool {
+        false
+    }
+}
+
+/* allow sharing a rule between reactors */
+impl<R: ReactionRule + ?Sized> ReactionRule for &R {
+    fn reacts(&self, prev: char, next: char) -> bool {
+        (**self).reacts(prev, next)
+    }
+
+    fn confluent(&self) -> bool {
+        (**self).confluent()
+    }
+}
+
+/* {{{ Case rules */
+
+/* Same ASCII letter with opposite cases, as in the puzzle statement. Other units never react. */
+pub struct AsciiCase;
+
+impl ReactionRule for AsciiCase {
+    fn reacts(&self, prev: char, next: char) -> bool {
+        prev != next && prev.is_ascii_alphabetic() && prev.eq_ignore_ascii_case(&next)
+    }
+
+    fn confluent(&self) -> bool {
+        true
+    }
+}
+
+/* Same letter with opposite cases, for any Unicode letter. */
+pub struct UnicodeCase;
+
+impl ReactionRule for UnicodeCase {
+    fn reacts(&self, prev: char, next: char) -> bool {
+        prev != next
+            && prev.is_lowercase() != next.is_lowercase()
+            && prev.to_lowercase().eq(next.to_lowercase())
+    }
+
+    fn confluent(&self) -> bool {
+        true
+    }
+}
+
+/* }}} */
+/* {{{ Pair table */
+
+/* Explicit list of the pairs of units that annihilate. Pairs are ordered: "( )" makes a "("
+ * followed by a ")" react, but not the opposite. With "a b" and "b c", "abc" reduces to "c" or
+ * to "a" depending on the reaction order, so tables are never assumed confluent.
+ */
+#[derive(Default)]
+pub struct PairTable {
+    pairs: HashSet<(char, char)>,
+}
+
+impl PairTable {
+    pub fn insert(&mut self, prev: char, next: char) {
+        self.pairs.insert((prev, next));
+    }
+
+    /* Load a table from a file, see the FromStr implementation for the format. */
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?.parse()?)
+    }
+}
+
+impl ReactionRule for PairTable {
+    fn reacts(&self, prev: char, next: char) -> bool {
+        self.pairs.contains(&(prev, next))
+    }
+}
+
+/* One pair per line, with the two units separated by whitespace. Empty lines and lines starting
+ * with '#' are ignored.
+ */
+impl FromStr for PairTable {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut table = PairTable::default();
+
+        /* keep the line numbers of the file in errors */
+        let lines: Vec<TableLine> = parse_lines(s)?;
+        for line in lines {
+            if let TableLine::Pair(prev, next) = line {
+                table.insert(prev, next);
+            }
+        }
+        Ok(table)
+    }
+}
+
+enum TableLine {
+    /* empty line or comment */
+    Blank,
+    Pair(char, char),
+}
+
+const PAIR_PATTERN: &str = "{unit} {unit}";
+
+impl FromStr for TableLine {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let s = s.trim();
+        if s.is_empty() || s.starts_with('#') {
+            return Ok(TableLine::Blank);
+        }
+
+        let mut units = s.split_whitespace().map(|unit| {
+            let mut chars = unit.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        });
+        let prev = units.next().flatten();
+        let next = units.next().flatten();
+        if units.next().is_some() {
+            return Err(ParseError::new(s, PAIR_PATTERN));
+        }
+
+        let prev = prev.ok_or_else(|| ParseError::new(s, PAIR_PATTERN).with_field("first unit"))?;
+        let next =
+            next.ok_or_else(|| ParseError::new(s, PAIR_PATTERN).with_field("second unit"))?;
+        Ok(TableLine::Pair(prev, next))
+    }
+}
+
+/* }}} */
+/* {{{ Rule */
+
+/* Reaction rule selected at runtime */
+#[derive(Default)]
+pub enum Rule {
+    #[default]
+    AsciiCase,
+    UnicodeCase,
+    Table(PairTable),
+}
+
+impl ReactionRule for Rule {
+    fn reacts(&self, prev: char, next: char) -> bool {
+        match self {
+            Rule::AsciiCase => AsciiCase.reacts(prev, next),
+            Rule::UnicodeCase => UnicodeCase.reacts(prev, next),
+            Rule::Table(table) => table.reacts(prev, next),
+        }
+    }
+
+    fn confluent(&self) -> bool {
+        match self {
+            Rule::AsciiCase => AsciiCase.confluent(),
+            Rule::UnicodeCase => UnicodeCase.confluent(),
+            Rule::Table(table) => table.confluent(),
+        }
+    }
+}
+
+/* }}} */
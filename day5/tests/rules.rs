@@ -0,0 +1,63 @@
+use aoc18_rust_day5::{
+    lengths_without_each_unit, AsciiCase, PairTable, ReactionRule, Reactor, UnicodeCase,
+};
+
+fn reduce<R: ReactionRule>(polymer: &str, rule: R) -> String {
+    let mut reactor = Reactor::new(rule);
+    reactor.feed(polymer);
+    reactor.polymer().to_owned()
+}
+
+#[test]
+fn ascii_case_only_reacts_letters() {
+    assert_eq!(reduce("dabAcCaCBAcCcaDA", AsciiCase), "dabCBAcaDA");
+    /* '@' and '`' are 32 apart, as 'A' and 'a' */
+    assert_eq!(reduce("@`1!", AsciiCase), "@`1!");
+    assert_eq!(reduce("Éé", AsciiCase), "Éé");
+}
+
+#[test]
+fn unicode_case_folds_any_letter() {
+    assert_eq!(reduce("xÉéX", UnicodeCase), "");
+    assert_eq!(reduce("ΣσΔδ", UnicodeCase), "");
+    assert_eq!(reduce("ÉÉ", UnicodeCase), "ÉÉ");
+    assert_eq!(reduce("1!", UnicodeCase), "1!");
+}
+
+#[test]
+fn pair_table_reacts_listed_pairs() {
+    let table: PairTable = "# brackets\n( )\n[ ]\n\n< >\n".parse().unwrap();
+
+    assert_eq!(reduce("([]<>)", &table), "");
+    assert_eq!(reduce("([)]", &table), "([)]");
+    /* pairs are ordered */
+    assert_eq!(reduce(")(", &table), ")(");
+    assert_eq!(reduce("aA", &table), "aA");
+
+    let lengths = lengths_without_each_unit("([)]", &table);
+    assert_eq!(lengths, vec![(')', 1), ('[', 1), ('(', 3), (']', 3)]);
+}
+
+#[test]
+fn pair_table_removes_units_before_reacting() {
+    let table: PairTable = "a b\nb c\n".parse().unwrap();
+
+    /* "abc" reduces to "c", but "bc" reduces to nothing */
+    assert_eq!(reduce("abc", &table), "c");
+    let lengths = lengths_without_each_unit("abc", &table);
+    assert_eq!(lengths, vec![('a', 0), ('c', 0), ('b', 2)]);
+}
+
+#[test]
+fn pair_table_errors() {
+    let err = "( )\nab c\n".parse::<PairTable>().err().unwrap();
+    assert_eq!(err.line, Some(2));
+    assert_eq!(err.field, Some("first unit"));
+
+    let err = "( ) ]".parse::<PairTable>().err().unwrap();
+    assert_eq!(err.line, Some(1));
+    assert_eq!(err.field, None);
+
+    let err = "(".parse::<PairTable>().err().unwrap();
+    assert_eq!(err.field, Some("second unit"));
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::io;
+use std::io::Read;
+
+use aoc18_rust_day5::{
+    react_stream, react_stream_traced, AsciiCase, PolymerGenerator, ReactionStats, Reactor,
+    UnicodeCase,
+};
+
+/* Reader returning at most one byte per read, to react across every chunk boundary */
+struct ByteReader<R: Read>(R);
+
+impl<R: Read> Read for ByteReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = std::cmp::min(buf.len(), 1);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn stream_matches_committed_input() {
+    let input = fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+
+    assert_eq!(react_stream(&input[..], AsciiCase).unwrap(), 9348);
+    assert_eq!(
+        react_stream(ByteReader(&input[..]), AsciiCase).unwrap(),
+        9348
+    );
+}
+
+#[test]
+fn stream_matches_generated_polymer() {
+    let mut polymer = Vec::new();
+    PolymerGenerator::new(200_000, 42, 4)
+        .read_to_end(&mut polymer)
+        .unwrap();
+    assert_eq!(polymer.len(), 200_000);
+
+    let mut reactor = Reactor::new(AsciiCase);
+    reactor.feed(std::str::from_utf8(&polymer).unwrap());
+
+    let len = react_stream(PolymerGenerator::new(200_000, 42, 4), AsciiCase).unwrap();
+    assert_eq!(len, reactor.len());
+}
+
+#[test]
+fn stream_ignores_whitespace() {
+    assert_eq!(
+        react_stream(&b"dabAcCaCBAcCcaDA\n"[..], AsciiCase).unwrap(),
+        10
+    );
+    assert_eq!(
+        react_stream(&b"dabAc\nCaCBAcCcaDA\r\n"[..], AsciiCase).unwrap(),
+        10
+    );
+}
+
+#[test]
+fn stream_decodes_split_characters() {
+    let polymer = "ÀàbÉéB\u{3a3}x\u{3c3}X";
+
+    assert_eq!(react_stream(polymer.as_bytes(), UnicodeCase).unwrap(), 4);
+    assert_eq!(
+        react_stream(ByteReader(polymer.as_bytes()), UnicodeCase).unwrap(),
+        4
+    );
+}
+
+#[test]
+fn stream_rejects_invalid_utf8() {
+    let err = react_stream(&b"ab\xffBA"[..], AsciiCase).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let err = react_stream(&"aé".as_bytes()[..2], AsciiCase).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn trace_reactions() {
+    let mut reactions = Vec::new();
+    let mut stats = ReactionStats::default();
+
+    let len = react_stream_traced(&b"dabAc\nCaCBAcCcaDA"[..], AsciiCase, |reaction| {
+        stats.record(reaction);
+        reactions.push(reaction.to_string());
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(len, 10);
+
+    /* positions count the newline */
+    assert_eq!(reactions, vec!["4 c 6 C 4", "3 A 7 a 3", "11 c 12 C 6"]);
+    assert_eq!(stats.to_string(), "reactions: 3\nc: 2\na: 1");
+}
+
+#[test]
+fn trace_stops_on_error() {
+    let mut nb_reactions = 0;
+
+    let err = react_stream_traced(&b"aAbBcC"[..], AsciiCase, |_| {
+        nb_reactions += 1;
+        if nb_reactions == 2 {
+            Err(io::Error::other("full"))
+        } else {
+            Ok(())
+        }
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "full");
+    assert_eq!(nb_reactions, 2);
+}
+
+#[test]
+fn only_traced_reactors_report_reactions() {
+    let mut nb_reactions = 0;
+
+    let mut reactor = Reactor::new(UnicodeCase);
+    reactor
+        .feed_traced("ÉéxΣσ", |_| {
+            nb_reactions += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!((reactor.len(), nb_reactions), (1, 0));
+
+    let mut reactor = Reactor::traced(UnicodeCase);
+    reactor
+        .feed_traced("ÉéxΣσ", |_| {
+            nb_reactions += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!((reactor.len(), nb_reactions), (1, 2));
+}
@@ -0,0 +1,58 @@
+/* Compare the area engines on the committed input, and on generated inputs with more points over
+ * larger grids.
+ *
+ * Run with `cargo bench -p aoc18-rust-day6`, adding `--features rayon` to use every core.
+ */
+use std::fs;
+use std::time::Instant;
+
+use aoc18_rust_day6::{Day6, Engine};
+use common::{Solution, XorShift};
+
+const RUNS: u32 = 3;
+
+/* input listing random points of a square grid */
+fn generate_input(nb_points: u32, grid_size: u32) -> String {
+    XorShift::default()
+        .points(nb_points, grid_size)
+        .iter()
+        .map(|(x, y)| format!("{}, {}\n", x, y))
+        .collect()
+}
+
+fn bench(name: &str, input: &str, threshold: u32) {
+    for engine in &[Engine::Naive, Engine::Flood] {
+        let day6 = Day6 {
+            engine: *engine,
+            threshold,
+            ..Default::default()
+        };
+        let points = day6.parse(input).unwrap();
+        let start = Instant::now();
+        let mut answers = (String::new(), String::new());
+
+        for _ in 0..RUNS {
+            answers = (
+                day6.part1(&points).unwrap().to_string(),
+                day6.part2(&points).unwrap().to_string(),
+            );
+        }
+        println!(
+            "{}, {:?}: largest area {}, safe area {}, {:?} per run",
+            name,
+            engine,
+            answers.0,
+            answers.1,
+            start.elapsed() / RUNS
+        );
+    }
+}
+
+fn main() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+
+    bench("input", &input, 10_000);
+    /* thresholds keeping a safe region of a few percents of the grid */
+    bench("200 points", &generate_input(200, 1_000), 120_000);
+    bench("500 points", &generate_input(500, 2_000), 560_000);
+}
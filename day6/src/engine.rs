@@ -0,0 +1,311 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::ops::RangeInclusive;
+
+use crate::{unique_min, Bounds, Metric, Point};
+
+/* {{{ Closest points */
+
+/* Closest point of every location of the bounding box, row by row, None when several points are
+ * the closest. Every location is compared to every point, one row per thread with rayon.
+ */
+pub(crate) fn naive_labels<M: Metric>(
+    points: &[Point],
+    metric: &M,
+    bounds: &Bounds,
+) -> Vec<Option<usize>> {
+    let width = bounds.width();
+    let mut labels = vec![None; width * bounds.height()];
+    let label_row = |(row, labels): (usize, &mut [Option<usize>])| {
+        let y = bounds.min_y + row as i32;
+
+        for (col, label) in labels.iter_mut().enumerate() {
+            *label = closest_point(points, metric, bounds.min_x + col as i32, y);
+        }
+    };
+
+    #[cfg(feature = "rayon")]
+    labels.par_chunks_mut(width).enumerate().for_each(label_row);
+    #[cfg(not(feature = "rayon"))]
+    labels.chunks_mut(width).enumerate().for_each(label_row);
+    labels
+}
+
+/* Same labels as naive_labels, from a breadth-first flood fill started from every point at once,
+ * for metrics where the distance is the number of steps between locations.
+ *
+ * The locations at distance d of the points are reached from their neighbours at distance d - 1,
+ * whose closest points are all at distance d. A location is thus tied when reached from tied
+ * neighbours, or from neighbours closest to different points. Shortest paths between locations of
+ * the rectangle stay within it, so the fill does not need to go past it.
+ */
+pub(crate) fn flood_labels(
+    points: &[Point],
+    bounds: &Bounds,
+    steps: &[(i32, i32)],
+) -> Vec<Option<usize>> {
+    let (width, height) = (bounds.width() as i32, bounds.height() as i32);
+    let mut distances = vec![u32::MAX; bounds.width() * bounds.height()];
+    let mut labels = vec![None; distances.len()];
+    let mut frontier = Vec::with_capacity(points.len());
+
+    for (idx, point) in points.iter().enumerate() {
+        let cell = bounds.index(point.x, point.y);
+
+        if distances[cell] == 0 {
+            /* several points at the same location */
+            labels[cell] = None;
+        } else {
+            distances[cell] = 0;
+            labels[cell] = Some(idx);
+            frontier.push(cell);
+        }
+    }
+
+    let mut distance = 0;
+    let mut next = Vec::new();
+    while !frontier.is_empty() {
+        distance += 1;
+        for &cell in &frontier {
+            let label = labels[cell];
+            let (col, row) = (
+                (cell % bounds.width()) as i32,
+                (cell / bounds.width()) as i32,
+            );
+
+            for &(dx, dy) in steps {
+                let (col, row) = (col + dx, row + dy);
+                if col < 0 || col >= width || row < 0 || row >= height {
+                    continue;
+                }
+                let neighbour = (row * width + col) as usize;
+
+                if distances[neighbour] == u32::MAX {
+                    distances[neighbour] = distance;
+                    labels[neighbour] = label;
+                    next.push(neighbour);
+                } else if distances[neighbour] == distance && labels[neighbour] != label {
+                    labels[neighbour] = None;
+                }
+            }
+        }
+        std::mem::swap(&mut frontier, &mut next);
+        next.clear();
+    }
+    labels
+}
+
+fn closest_point<M: Metric>(points: &[Point], metric: &M, x: i32, y: i32) -> Option<usize> {
+    unique_min(points, |p| {
+        let (dx, dy) = p.deltas(i64::from(x), i64::from(y));
+        metric.rank(dx, dy)
+    })
+}
+
+/* }}} */
+/* {{{ Total distances */
+
+/* Columns and rows of the locations which may be safe.
+ *
+ * The metrics grow with the difference of each coordinate, so a location at distance d from the
+ * bounding box is at least min(distance(d, 0), distance(0, d)) away from every point, and safe
+ * locations are close enough to the bounding box for the points to be under the threshold.
+ */
+fn safe_candidates<M: Metric>(
+    points: &[Point],
+    metric: &M,
+    threshold: u32,
+) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+    let bounds = Bounds::of(points);
+    let nb_points = points.len() as f64;
+    let unit = metric.distance(1, 0).min(metric.distance(0, 1));
+    let margin = (f64::from(threshold) / (nb_points * unit)).ceil() as i64;
+
+    (
+        i64::from(bounds.min_x) - margin..=i64::from(bounds.max_x) + margin,
+        i64::from(bounds.min_y) - margin..=i64::from(bounds.max_y) + margin,
+    )
+}
+
+fn is_safe<M: Metric>(points: &[Point], metric: &M, threshold: u32, x: i64, y: i64) -> bool {
+    let total: f64 = points
+        .iter()
+        .map(|p| {
+            let (dx, dy) = p.deltas(x, y);
+            metric.distance(dx, dy)
+        })
+        .sum();
+    total < f64::from(threshold)
+}
+
+/* Number of locations whose total distance to the points is under the threshold, summing the
+ * distances to every point, one row per thread with rayon.
+ */
+pub(crate) fn naive_safe_count<M: Metric>(points: &[Point], metric: &M, threshold: u32) -> u64 {
+    let (columns, rows) = safe_candidates(points, metric, threshold);
+    let count_row = |y: i64| {
+        columns
+            .clone()
+            .filter(|&x| is_safe(points, metric, threshold, x, y))
+            .count() as u64
+    };
+
+    #[cfg(feature = "rayon")]
+    return rows.into_par_iter().map(count_row).sum();
+    #[cfg(not(feature = "rayon"))]
+    rows.map(count_row).sum()
+}
+
+/* Safe locations of each column with some, as (x, first y, last y), the columns in order, summing
+ * the distances to every point, one column per thread with rayon. The total distance is convex,
+ * so the safe locations of a column are contiguous.
+ */
+pub(crate) fn naive_safe_columns<M: Metric>(
+    points: &[Point],
+    metric: &M,
+    threshold: u32,
+) -> Vec<(i64, i64, i64)> {
+    let (columns, rows) = safe_candidates(points, metric, threshold);
+    let column = |x: i64| {
+        let mut safe = rows
+            .clone()
+            .filter(|&y| is_safe(points, metric, threshold, x, y));
+        let first = safe.next()?;
+        Some((x, first, safe.next_back().unwrap_or(first)))
+    };
+
+    #[cfg(feature = "rayon")]
+    return columns.into_par_iter().filter_map(column).collect();
+    #[cfg(not(feature = "rayon"))]
+    columns.filter_map(column).collect()
+}
+
+/* Same columns as naive_safe_columns, for a weighted sum of the distances along each axis.
+ *
+ * The total distance is the weighted sum of the total distances along each axis, computed once per
+ * column and once per row. Both are convex, so the safe locations of a column, and the columns with
+ * safe locations, are intervals found by binary search, one column per thread with rayon.
+ */
+pub(crate) fn safe_columns(
+    points: &[Point],
+    (wx, wy): (u64, u64),
+    threshold: u32,
+) -> Vec<(i64, i64, i64)> {
+    let bounds = Bounds::of(points);
+    let columns = AxisDistances::new(points.iter().map(|p| p.x), bounds.min_x, bounds.max_x);
+    let rows = AxisDistances::new(points.iter().map(|p| p.y), bounds.min_y, bounds.max_y);
+    let threshold = u64::from(threshold);
+
+    /* w * distance < limit exactly when distance < ceil(limit / w) */
+    let (first, last) = match threshold
+        .checked_sub(wy * rows.min())
+        .and_then(|limit| columns.under(limit.div_ceil(wx)))
+    {
+        Some(range) => range,
+        None => return Vec::new(),
+    };
+    /* cannot fail, the column has safe locations */
+    let column = |x: i64| {
+        let limit = threshold - wx * columns.at(x);
+        let (y1, y2) = rows.under(limit.div_ceil(wy)).unwrap();
+        (x, y1, y2)
+    };
+
+    #[cfg(feature = "rayon")]
+    return (first..=last).into_par_iter().map(column).collect();
+    #[cfg(not(feature = "rayon"))]
+    (first..=last).map(column).collect()
+}
+
+/* Total distance along one axis from every coordinate to the coordinates of the points */
+struct AxisDistances {
+    lo: i64,
+    /* total distances from each coordinate of the range of the points */
+    distances: Vec<u64>,
+    /* position of the smallest total distance in the range */
+    argmin: usize,
+    nb_coords: u64,
+}
+
+impl AxisDistances {
+    /* Computed in O(coordinates + range), the range containing every coordinate: moving one
+     * step further adds one per coordinate behind, and removes one per coordinate ahead.
+     */
+    fn new<I>(coords: I, lo: i32, hi: i32) -> Self
+    where
+        I: Iterator<Item = i32>,
+    {
+        let len = (hi - lo) as usize + 1;
+        /* number of coordinates at each position of the range */
+        let mut counts = vec![0i64; len];
+        let mut total = 0;
+        for c in coords {
+            counts[(c - lo) as usize] += 1;
+            total += i64::from(c - lo);
+        }
+        let nb_coords: i64 = counts.iter().sum();
+
+        let mut distances = Vec::with_capacity(len);
+        let mut behind = 0;
+        for count in counts {
+            distances.push(total as u64);
+            behind += count;
+            total += behind - (nb_coords - behind);
+        }
+        let argmin = (0..len).min_by_key(|&i| distances[i]).unwrap();
+
+        AxisDistances {
+            lo: i64::from(lo),
+            distances,
+            argmin,
+            nb_coords: nb_coords as u64,
+        }
+    }
+
+    fn hi(&self) -> i64 {
+        self.lo + self.distances.len() as i64 - 1
+    }
+
+    fn min(&self) -> u64 {
+        self.distances[self.argmin]
+    }
+
+    /* past the range, each step away from it adds one per coordinate */
+    fn at(&self, c: i64) -> u64 {
+        if c < self.lo {
+            self.distances[0] + self.nb_coords * (self.lo - c) as u64
+        } else if c > self.hi() {
+            self.distances[self.distances.len() - 1] + self.nb_coords * (c - self.hi()) as u64
+        } else {
+            self.distances[(c - self.lo) as usize]
+        }
+    }
+
+    /* First and last coordinates whose total distance is under the limit, those in between being
+     * under it too: the distances decrease until argmin, and increase after it.
+     */
+    fn under(&self, limit: u64) -> Option<(i64, i64)> {
+        if self.min() >= limit {
+            return None;
+        }
+        let (decreasing, increasing) = self.distances.split_at(self.argmin);
+        let last_distance = *increasing.last().unwrap();
+
+        /* number of steps past the range still under the limit */
+        let steps_past = |distance: u64| ((limit - distance - 1) / self.nb_coords) as i64;
+        let first = if self.distances[0] < limit {
+            self.lo - steps_past(self.distances[0])
+        } else {
+            self.lo + decreasing.partition_point(|&d| d >= limit) as i64
+        };
+        let last = if last_distance < limit {
+            self.hi() + steps_past(last_distance)
+        } else {
+            self.lo + self.argmin as i64 + increasing.partition_point(|&d| d < limit) as i64 - 1
+        };
+        Some((first, last))
+    }
+}
+
+/* }}} */
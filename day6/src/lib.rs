@@ -0,0 +1,434 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use scan_fmt::scan_fmt;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+mod engine;
+mod metric;
+
+pub use metric::{Chebyshev, Euclidean, Manhattan, Metric, MetricKind, WeightedManhattan};
+
+pub struct Day6 {
+    /* total distance to all points under which a location is safe */
+    pub threshold: u32,
+    /* report the area of every point instead of the largest finite area, and the shape of the
+     * safe region along with its area
+     */
+    pub report: bool,
+    pub engine: Engine,
+    pub metric: MetricKind,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Day6 {
+            threshold: 10_000,
+            report: false,
+            engine: Engine::default(),
+            metric: MetricKind::default(),
+        }
+    }
+}
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<Point>;
+    type Answer1 = LargestArea;
+    type Answer2 = SafeArea;
+
+    fn parse(&self, input: &str) -> Result<Vec<Point>> {
+        let points: Vec<Point> = parse_lines(input)?;
+
+        if points.is_empty() {
+            return Err(Box::from("no points in the input"));
+        }
+        Ok(points)
+    }
+
+    fn part1(&self, points: &Vec<Point>) -> Result<LargestArea> {
+        if self.report {
+            let areas = point_areas(points, &self.metric, self.engine)
+                .into_iter()
+                .zip(points)
+                .map(|(area, &point)| PointArea { point, area })
+                .collect();
+            return Ok(LargestArea::Report(areas));
+        }
+        largest_area(points, &self.metric, self.engine)
+            .map(LargestArea::Largest)
+            .ok_or_else(|| Box::<dyn Error>::from("every area is infinite"))
+    }
+
+    fn part2(&self, points: &Vec<Point>) -> Result<SafeArea> {
+        if self.report {
+            let region = safe_region(points, self.threshold, &self.metric);
+            return Ok(SafeArea::Report(region));
+        }
+        let area = safe_area(points, self.threshold, &self.metric, self.engine);
+        Ok(SafeArea::Count(area))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "threshold" => self.threshold = value.parse()?,
+            "report" => self.report = value.parse()?,
+            "engine" => self.engine = value.parse()?,
+            "metric" => self.metric = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 6", name).into()),
+        }
+        Ok(())
+    }
+}
+
+/* Largest finite area, or the area of every point, one per line */
+pub enum LargestArea {
+    Largest(u32),
+    Report(Vec<PointArea>),
+}
+
+impl fmt::Display for LargestArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LargestArea::Largest(area) => write!(f, "{}", area),
+            LargestArea::Report(areas) => {
+                let rows: Vec<String> = areas.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}", rows.join("\n"))
+            }
+        }
+    }
+}
+
+/* Number of locations closest to a point, None if infinite */
+#[derive(Debug, PartialEq)]
+pub struct PointArea {
+    pub point: Point,
+    pub area: Option<u32>,
+}
+
+impl fmt::Display for PointArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.area {
+            Some(area) => write!(f, "{}: {}", self.point, area),
+            None => write!(f, "{}: unbounded", self.point),
+        }
+    }
+}
+
+/* Number of safe locations, or the shape of the safe region, one property per line */
+pub enum SafeArea {
+    Count(u64),
+    Report(SafeRegion),
+}
+
+impl fmt::Display for SafeArea {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SafeArea::Count(area) => write!(f, "{}", area),
+            SafeArea::Report(region) => write!(f, "{}", region),
+        }
+    }
+}
+
+/* Locations whose total distance to the points is under the threshold */
+#[derive(Debug, PartialEq)]
+pub struct SafeRegion {
+    pub area: u64,
+    /* first and last column and row with safe locations, None without any */
+    pub bounds: Option<((i64, i64), (i64, i64))>,
+    /* number of sides between a safe location and an unsafe one */
+    pub perimeter: u64,
+    /* whether every safe location can be reached from the others through safe locations */
+    pub connected: bool,
+}
+
+impl fmt::Display for SafeRegion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "area: {}", self.area)?;
+        match self.bounds {
+            Some(((x1, y1), (x2, y2))) => {
+                writeln!(f, "bounding box: {}, {} to {}, {}", x1, y1, x2, y2)?
+            }
+            None => writeln!(f, "bounding box: none")?,
+        }
+        writeln!(f, "perimeter: {}", self.perimeter)?;
+        write!(
+            f,
+            "connected: {}",
+            if self.connected { "yes" } else { "no" }
+        )
+    }
+}
+
+/* {{{ Engine */
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Engine {
+    /* distances from every location to every point, kept for comparison */
+    Naive,
+    /* flood fill from every point at once, and total distances summed along each axis, for the
+     * metrics allowing them, the naive engine being used otherwise
+     */
+    #[default]
+    Flood,
+}
+
+impl FromStr for Engine {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "naive" => Ok(Engine::Naive),
+            "flood" => Ok(Engine::Flood),
+            _ => Err(format!("unknown engine {}, expected naive or flood", s).into()),
+        }
+    }
+}
+
+/* }}} */
+/* {{{ Areas */
+
+/* Smallest rectangle containing every point, bounds included */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min_x: i32,
+    pub min_y: i32,
+    pub max_x: i32,
+    pub max_y: i32,
+}
+
+impl Bounds {
+    pub fn of(points: &[Point]) -> Self {
+        Bounds {
+            min_x: points.iter().map(|p| p.x).min().unwrap(),
+            min_y: points.iter().map(|p| p.y).min().unwrap(),
+            max_x: points.iter().map(|p| p.x).max().unwrap(),
+            max_y: points.iter().map(|p| p.y).max().unwrap(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y) as usize + 1
+    }
+
+    /* index of a location of the rectangle, row by row */
+    fn index(&self, x: i32, y: i32) -> usize {
+        (y - self.min_y) as usize * self.width() + (x - self.min_x) as usize
+    }
+}
+
+/* Number of locations closest to each point, None for the points whose area is infinite. */
+pub fn point_areas<M: Metric>(points: &[Point], metric: &M, engine: Engine) -> Vec<Option<u32>> {
+    let mut unbounded = vec![false; points.len()];
+    for idx in metric.unbounded_points(points) {
+        unbounded[idx] = true;
+    }
+
+    let bounds = metric.finite_bounds(points, &unbounded);
+    let labels = match (engine, metric.flood_steps()) {
+        (Engine::Flood, Some(steps)) => engine::flood_labels(points, &bounds, steps),
+        _ => engine::naive_labels(points, metric, &bounds),
+    };
+
+    let mut areas = vec![Some(0); points.len()];
+    for idx in labels.into_iter().flatten() {
+        areas[idx] = areas[idx].map(|area| area + 1);
+    }
+    for (area, _) in areas.iter_mut().zip(&unbounded).filter(|(_, &u)| u) {
+        *area = None;
+    }
+    areas
+}
+
+fn largest_area<M: Metric>(points: &[Point], metric: &M, engine: Engine) -> Option<u32> {
+    point_areas(points, metric, engine)
+        .into_iter()
+        .flatten()
+        .max()
+}
+
+/* Indexes of the points whose area is infinite, in input order. */
+pub fn unbounded_points<M: Metric>(points: &[Point], metric: &M) -> Vec<usize> {
+    metric.unbounded_points(points)
+}
+
+/* Indexes of the points whose area is infinite with a weighted sum of the distances along each
+ * axis, in input order.
+ *
+ * Past the bounding box, moving away from it increases the distances to every point by the same
+ * amount, so the closest point does not change. An area is thus infinite exactly when its point
+ * is the only closest point of locations infinitely far in one of the four directions, the other
+ * coordinate being within the bounding box: the point dominates this direction.
+ */
+pub(crate) fn dominating_points(points: &[Point], (wx, wy): (i64, i64)) -> Vec<usize> {
+    let bounds = Bounds::of(points);
+    let mut unbounded = vec![false; points.len()];
+
+    /* towards +y, -y, +x then -x */
+    for &(vertical, sign) in &[(true, 1), (true, -1), (false, 1), (false, -1)] {
+        let (min, max, w_along, w_across) = if vertical {
+            (bounds.min_x, bounds.max_x, wy, wx)
+        } else {
+            (bounds.min_y, bounds.max_y, wx, wy)
+        };
+
+        for c in min..=max {
+            /* distance to a location far away at coordinate c on the other axis, minus the
+             * distance from this location to the bounding box
+             */
+            let far_distance = |p: &Point| {
+                let (along, across) = if vertical { (p.y, p.x) } else { (p.x, p.y) };
+                w_across * i64::from(across.abs_diff(c)) - sign * w_along * i64::from(along)
+            };
+            if let Some(idx) = unique_min(points, far_distance) {
+                unbounded[idx] = true;
+            }
+        }
+    }
+
+    (0..points.len()).filter(|&idx| unbounded[idx]).collect()
+}
+
+/* Number of locations whose total distance to the points is under the threshold, wherever they
+ * are.
+ */
+pub fn safe_area<M: Metric>(points: &[Point], threshold: u32, metric: &M, engine: Engine) -> u64 {
+    match (engine, metric.axis_weights()) {
+        (Engine::Flood, Some(weights)) => engine::safe_columns(points, weights, threshold)
+            .iter()
+            .map(|&(_, y1, y2)| (y2 - y1 + 1) as u64)
+            .sum(),
+        _ => engine::naive_safe_count(points, metric, threshold),
+    }
+}
+
+/* Area and shape of the locations whose total distance to the points is under the threshold. */
+pub fn safe_region<M: Metric>(points: &[Point], threshold: u32, metric: &M) -> SafeRegion {
+    let columns = match metric.axis_weights() {
+        Some(weights) => engine::safe_columns(points, weights, threshold),
+        None => engine::naive_safe_columns(points, metric, threshold),
+    };
+    let len = |y1: i64, y2: i64| (y2 - y1 + 1) as u64;
+
+    let mut region = SafeRegion {
+        area: 0,
+        bounds: None,
+        perimeter: 0,
+        connected: true,
+    };
+    for (pos, &(x, y1, y2)) in columns.iter().enumerate() {
+        region.area += len(y1, y2);
+        region.bounds = Some(match region.bounds {
+            None => ((x, y1), (x, y2)),
+            Some(((x1, min_y), (_, max_y))) => ((x1, min_y.min(y1)), (x, max_y.max(y2))),
+        });
+
+        /* top and bottom sides, and the sides with the previous column, or the left sides */
+        region.perimeter += 2;
+        match pos.checked_sub(1).map(|prev| columns[prev]) {
+            Some((prev_x, p1, p2)) if prev_x + 1 == x => {
+                let overlap = (p2.min(y2) - p1.max(y1) + 1).max(0) as u64;
+                region.perimeter += len(p1, p2) + len(y1, y2) - 2 * overlap;
+                region.connected &= overlap > 0;
+            }
+            Some((_, p1, p2)) => {
+                /* columns without safe locations in between */
+                region.perimeter += len(p1, p2) + len(y1, y2);
+                region.connected = false;
+            }
+            None => region.perimeter += len(y1, y2),
+        }
+    }
+    /* right sides of the last column */
+    if let Some(&(_, y1, y2)) = columns.last() {
+        region.perimeter += len(y1, y2);
+    }
+    region
+}
+
+/* Index of the point with the smallest key, None if several points have it. */
+pub(crate) fn unique_min<K, F>(points: &[Point], key: F) -> Option<usize>
+where
+    K: Ord,
+    F: Fn(&Point) -> K,
+{
+    let mut closest = None;
+    let mut min_key = None;
+
+    for (index, point) in points.iter().enumerate() {
+        let k = key(point);
+
+        if min_key.as_ref() == Some(&k) {
+            closest = None;
+        } else if min_key.as_ref().is_none_or(|min| k < *min) {
+            min_key = Some(k);
+            closest = Some(index);
+        }
+    }
+
+    closest
+}
+
+/* }}} */
+/* {{{ Point */
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /* Differences of the coordinates with a location, the largest ones standing for any
+     * location too far away to matter.
+     */
+    fn deltas(&self, x: i64, y: i64) -> (u32, u32) {
+        let delta = |a: i64, b: i32| u32::try_from(a.abs_diff(i64::from(b))).unwrap_or(u32::MAX);
+
+        (delta(x, self.x), delta(y, self.y))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}", self.x, self.y)
+    }
+}
+
+const POINT_PATTERN: &str = "{d}, {d}";
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (x, y) = scan_fmt!(s, POINT_PATTERN, i32, i32);
+
+        Ok(Point {
+            x: field(x, "x", s, POINT_PATTERN)?,
+            y: field(y, "y", s, POINT_PATTERN)?,
+        })
+    }
+}
+
+/* }}} */
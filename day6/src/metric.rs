@@ -0,0 +1,394 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::{dominating_points, Bounds, Point, Result};
+
+/* Distance between two locations, growing with the difference of each coordinate */
+pub trait Metric: Sync {
+    /* distance between two locations dx and dy apart along each axis */
+    fn distance(&self, dx: u32, dy: u32) -> f64;
+
+    /* integer in the same order as the distance, to find the closest points exactly */
+    fn rank(&self, dx: u32, dy: u32) -> u64;
+
+    /* Weights of each axis, when the distance is the weighted sum of the distances along each
+     * axis. Total distances can then be computed along each axis, and no location past the
+     * bounding box of the points is closest to a point with a finite area.
+     */
+    fn axis_weights(&self) -> Option<(u64, u64)> {
+        None
+    }
+
+    /* Steps of a flood fill reaching the locations by order of distance, if there are some. */
+    fn flood_steps(&self) -> Option<&'static [(i32, i32)]> {
+        None
+    }
+
+    /* Indexes of the points whose area is infinite, in input order. */
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize>;
+
+    /* Rectangle containing the points and every location closest to a point with a finite area,
+     * the bounding box of the points by default. With weighted sums of the distances along each
+     * axis, moving away from the bounding box increases the distances to every point by the same
+     * amount: the locations past it have the same closest points as locations infinitely far.
+     */
+    fn finite_bounds(&self, points: &[Point], _unbounded: &[bool]) -> Bounds {
+        Bounds::of(points)
+    }
+}
+
+const ORTHOGONAL_STEPS: &[(i32, i32)] = &[(-1, 0), (1, 0), (0, -1), (0, 1)];
+const KING_STEPS: &[(i32, i32)] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/* {{{ Metrics */
+
+/* Taxicab distance, the distance of the puzzle */
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+    fn distance(&self, dx: u32, dy: u32) -> f64 {
+        f64::from(dx) + f64::from(dy)
+    }
+
+    fn rank(&self, dx: u32, dy: u32) -> u64 {
+        u64::from(dx) + u64::from(dy)
+    }
+
+    fn axis_weights(&self) -> Option<(u64, u64)> {
+        Some((1, 1))
+    }
+
+    fn flood_steps(&self) -> Option<&'static [(i32, i32)]> {
+        Some(ORTHOGONAL_STEPS)
+    }
+
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize> {
+        dominating_points(points, (1, 1))
+    }
+}
+
+/* Taxicab distance, each axis costing its own weight per step */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedManhattan {
+    pub wx: u32,
+    pub wy: u32,
+}
+
+impl Metric for WeightedManhattan {
+    fn distance(&self, dx: u32, dy: u32) -> f64 {
+        f64::from(self.wx) * f64::from(dx) + f64::from(self.wy) * f64::from(dy)
+    }
+
+    fn rank(&self, dx: u32, dy: u32) -> u64 {
+        u64::from(self.wx) * u64::from(dx) + u64::from(self.wy) * u64::from(dy)
+    }
+
+    fn axis_weights(&self) -> Option<(u64, u64)> {
+        Some((u64::from(self.wx), u64::from(self.wy)))
+    }
+
+    fn flood_steps(&self) -> Option<&'static [(i32, i32)]> {
+        /* a scaled manhattan distance */
+        if self.wx == self.wy {
+            Some(ORTHOGONAL_STEPS)
+        } else {
+            None
+        }
+    }
+
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize> {
+        dominating_points(points, (i64::from(self.wx), i64::from(self.wy)))
+    }
+}
+
+/* Number of king moves, the largest distance along an axis */
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+    fn distance(&self, dx: u32, dy: u32) -> f64 {
+        f64::from(dx.max(dy))
+    }
+
+    fn rank(&self, dx: u32, dy: u32) -> u64 {
+        u64::from(dx.max(dy))
+    }
+
+    fn flood_steps(&self) -> Option<&'static [(i32, i32)]> {
+        Some(KING_STEPS)
+    }
+
+    /* The distance is half the manhattan distance once the grid is rotated by 45 degrees, from
+     * (x, y) to (x + y, x - y). Every location of the rotated grid with the same coordinate
+     * along an axis can be reached far away along the other axis, so the areas are infinite for
+     * the same points.
+     */
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize> {
+        let rotated: Vec<Point> = points
+            .iter()
+            .map(|p| Point::new(p.x + p.y, p.x - p.y))
+            .collect();
+
+        dominating_points(&rotated, (1, 1))
+    }
+
+    /* the bounding box of the rotated points, rotated back */
+    fn finite_bounds(&self, points: &[Point], _unbounded: &[bool]) -> Bounds {
+        let sums = points.iter().map(|p| i64::from(p.x) + i64::from(p.y));
+        let diffs = points.iter().map(|p| i64::from(p.x) - i64::from(p.y));
+        let (min_u, max_u) = (sums.clone().min().unwrap(), sums.max().unwrap());
+        let (min_v, max_v) = (diffs.clone().min().unwrap(), diffs.max().unwrap());
+
+        Bounds {
+            min_x: to_coord((min_u + min_v).div_euclid(2)),
+            min_y: to_coord((min_u - max_v).div_euclid(2)),
+            max_x: to_coord((max_u + max_v + 1).div_euclid(2)),
+            max_y: to_coord((max_u - min_v + 1).div_euclid(2)),
+        }
+    }
+}
+
+/* Straight line distance */
+pub struct Euclidean;
+
+impl Metric for Euclidean {
+    fn distance(&self, dx: u32, dy: u32) -> f64 {
+        (self.rank(dx, dy) as f64).sqrt()
+    }
+
+    fn rank(&self, dx: u32, dy: u32) -> u64 {
+        u64::from(dx) * u64::from(dx) + u64::from(dy) * u64::from(dy)
+    }
+
+    /* The area of a point is infinite when the point is on the boundary of the convex hull of
+     * the points: it then contains a cone, or a half strip along a line of the grid, away from
+     * the hull. The areas of the points within the hull are finite.
+     */
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize> {
+        let mut locations: Vec<(i64, i64)> = points
+            .iter()
+            .map(|p| (i64::from(p.x), i64::from(p.y)))
+            .collect();
+        locations.sort_unstable();
+        let duplicated = |loc: &(i64, i64)| {
+            let start = locations.partition_point(|l| l < loc);
+            locations.get(start + 1) == Some(loc)
+        };
+        let hull = convex_hull(&locations);
+
+        (0..points.len())
+            .filter(|&idx| {
+                let loc = (i64::from(points[idx].x), i64::from(points[idx].y));
+                /* points at the same location have no area */
+                !duplicated(&loc) && on_hull_boundary(&hull, loc)
+            })
+            .collect()
+    }
+
+    fn finite_bounds(&self, points: &[Point], unbounded: &[bool]) -> Bounds {
+        let mut bounds = Bounds::of(points);
+
+        for (p, _) in points.iter().zip(unbounded).filter(|(_, &u)| !u) {
+            let (x, y) = (i64::from(p.x), i64::from(p.y));
+
+            if let Some(reach) = cell_reach(points, p, (1, 0)) {
+                bounds.max_x = bounds.max_x.max(to_coord(x + reach));
+            }
+            if let Some(reach) = cell_reach(points, p, (-1, 0)) {
+                bounds.min_x = bounds.min_x.min(to_coord(x - reach));
+            }
+            if let Some(reach) = cell_reach(points, p, (0, 1)) {
+                bounds.max_y = bounds.max_y.max(to_coord(y + reach));
+            }
+            if let Some(reach) = cell_reach(points, p, (0, -1)) {
+                bounds.min_y = bounds.min_y.min(to_coord(y - reach));
+            }
+        }
+        bounds
+    }
+}
+
+/* How far the locations closest to a point reach along a direction of the grid, None when no
+ * location is closest to the point, or when its area is infinite.
+ *
+ * The locations q at least as close to p as to another point r verify (q - p).d <= |d|^2 / 2,
+ * with d = r - p. The largest (q - p).e under these constraints is a linear program, whose dual
+ * minimizes the sum of l_r * |d_r|^2 / 2 over l_r >= 0 with e the sum of l_r * d_r. The optimum
+ * of the dual is reached with at most two points, so trying every pair of points gives the exact
+ * reach along e, without computing the cell itself.
+ */
+fn cell_reach(points: &[Point], p: &Point, (ex, ey): (i128, i128)) -> Option<i64> {
+    let deltas: Vec<(i128, i128)> = points
+        .iter()
+        .map(|r| (i128::from(r.x - p.x), i128::from(r.y - p.y)))
+        .filter(|&d| d != (0, 0))
+        .collect();
+    let norm = |(dx, dy): (i128, i128)| dx * dx + dy * dy;
+    let mut reach: Option<i128> = None;
+    let mut bound = |num: i128, den: i128| {
+        let candidate = num.div_euclid(den);
+        reach = Some(reach.map_or(candidate, |reach| reach.min(candidate)));
+    };
+
+    if deltas.len() < points.len() - 1 {
+        /* several points at this location, none of them being the closest */
+        return None;
+    }
+    for &r in &deltas {
+        /* r along e: l_r = 1 / |r| */
+        if r.0 * ey == r.1 * ex && r.0 * ex + r.1 * ey > 0 {
+            bound(norm(r), 2 * (r.0 * ex + r.1 * ey));
+        }
+        for &s in &deltas {
+            /* e = l_r * r + l_s * s, solved with Cramer's rule */
+            let det = r.0 * s.1 - r.1 * s.0;
+            let (lr, ls) = (ex * s.1 - ey * s.0, r.0 * ey - r.1 * ex);
+            if det > 0 && lr >= 0 && ls >= 0 {
+                bound(lr * norm(r) + ls * norm(s), 2 * det);
+            }
+        }
+    }
+    reach.map(|reach| reach.min(i128::from(i64::MAX)) as i64)
+}
+
+fn to_coord(c: i64) -> i32 {
+    c.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+}
+
+fn cross(o: (i64, i64), a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/* Corners of the convex hull of sorted locations, counterclockwise, with Andrew's monotone
+ * chain: locations along the edges are not corners.
+ */
+fn convex_hull(sorted: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let mut hull: Vec<(i64, i64)> = Vec::new();
+
+    for pass in 0..2 {
+        let start = hull.len();
+        let chain: Box<dyn Iterator<Item = &(i64, i64)>> = if pass == 0 {
+            Box::new(sorted.iter())
+        } else {
+            Box::new(sorted.iter().rev())
+        };
+
+        for &loc in chain {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], loc) <= 0
+            {
+                hull.pop();
+            }
+            hull.push(loc);
+        }
+        /* the last location of a chain is the first of the other one */
+        hull.pop();
+    }
+    if hull.is_empty() {
+        /* a single location */
+        hull.extend(sorted.first());
+    }
+    hull
+}
+
+fn on_hull_boundary(hull: &[(i64, i64)], loc: (i64, i64)) -> bool {
+    (0..hull.len()).any(|i| {
+        let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+
+        cross(a, b, loc) == 0
+            && a.0.min(b.0) <= loc.0
+            && loc.0 <= a.0.max(b.0)
+            && a.1.min(b.1) <= loc.1
+            && loc.1 <= a.1.max(b.1)
+    })
+}
+
+/* }}} */
+/* {{{ MetricKind */
+
+/* Metric selected at runtime */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MetricKind {
+    #[default]
+    Manhattan,
+    WeightedManhattan(WeightedManhattan),
+    Chebyshev,
+    Euclidean,
+}
+
+impl FromStr for MetricKind {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "manhattan" => Ok(MetricKind::Manhattan),
+            "chebyshev" => Ok(MetricKind::Chebyshev),
+            "euclidean" => Ok(MetricKind::Euclidean),
+            _ => {
+                let weights = s.strip_prefix("weighted:").and_then(|w| w.split_once(','));
+                let (wx, wy) = weights.ok_or_else(|| {
+                    format!(
+                        "unknown metric {}, expected manhattan, chebyshev, euclidean \
+                         or weighted:<wx>,<wy>",
+                        s
+                    )
+                })?;
+                let (wx, wy): (u32, u32) = (wx.parse()?, wy.parse()?);
+
+                if wx == 0 || wy == 0 {
+                    return Err(
+                        format!("invalid weights in {}, expected positive weights", s).into(),
+                    );
+                }
+                Ok(MetricKind::WeightedManhattan(WeightedManhattan { wx, wy }))
+            }
+        }
+    }
+}
+
+impl MetricKind {
+    fn metric(&self) -> &dyn Metric {
+        match self {
+            MetricKind::Manhattan => &Manhattan,
+            MetricKind::WeightedManhattan(metric) => metric,
+            MetricKind::Chebyshev => &Chebyshev,
+            MetricKind::Euclidean => &Euclidean,
+        }
+    }
+}
+
+impl Metric for MetricKind {
+    fn distance(&self, dx: u32, dy: u32) -> f64 {
+        self.metric().distance(dx, dy)
+    }
+
+    fn rank(&self, dx: u32, dy: u32) -> u64 {
+        self.metric().rank(dx, dy)
+    }
+
+    fn axis_weights(&self) -> Option<(u64, u64)> {
+        self.metric().axis_weights()
+    }
+
+    fn flood_steps(&self) -> Option<&'static [(i32, i32)]> {
+        self.metric().flood_steps()
+    }
+
+    fn unbounded_points(&self, points: &[Point]) -> Vec<usize> {
+        self.metric().unbounded_points(points)
+    }
+
+    fn finite_bounds(&self, points: &[Point], unbounded: &[bool]) -> Bounds {
+        self.metric().finite_bounds(points, unbounded)
+    }
+}
+
+/* }}} */
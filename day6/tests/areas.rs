@@ -0,0 +1,133 @@
+use aoc18_rust_day6::{
+    point_areas, unbounded_points, Bounds, Chebyshev, Engine, Euclidean, Manhattan, Metric,
+    MetricKind, Point, WeightedManhattan,
+};
+
+/* Areas counted on the bounding box grown by a margin, by brute force */
+fn areas_within<M: Metric>(points: &[Point], metric: &M, margin: i32) -> Vec<u32> {
+    let bounds = Bounds::of(points);
+    let mut areas = vec![0; points.len()];
+
+    for y in bounds.min_y - margin..=bounds.max_y + margin {
+        for x in bounds.min_x - margin..=bounds.max_x + margin {
+            let dists: Vec<u64> = points
+                .iter()
+                .map(|p| metric.rank(p.x().abs_diff(x), p.y().abs_diff(y)))
+                .collect();
+            let min = *dists.iter().min().unwrap();
+            if dists.iter().filter(|&&d| d == min).count() == 1 {
+                areas[dists.iter().position(|&d| d == min).unwrap()] += 1;
+            }
+        }
+    }
+    areas
+}
+
+/* Points whose area keeps growing past the margin, and the areas of the others */
+fn brute_force<M: Metric>(
+    points: &[Point],
+    metric: &M,
+    margin: i32,
+) -> (Vec<usize>, Vec<Option<u32>>) {
+    let small = areas_within(points, metric, margin);
+    let large = areas_within(points, metric, 2 * margin);
+    let growing = (0..points.len()).filter(|&i| large[i] > small[i]).collect();
+    let areas = (0..points.len())
+        .map(|i| Some(large[i]).filter(|_| large[i] == small[i]))
+        .collect();
+
+    (growing, areas)
+}
+
+fn points(coords: &[(i32, i32)]) -> Vec<Point> {
+    coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+}
+
+const CASES: &[&[(i32, i32)]] = &[
+    &[(0, 0)],
+    &[(0, 0), (0, 0), (5, 5)],
+    &[(0, 1), (1, 0)],
+    &[(0, 0), (2, 2), (4, 4)],
+    &[(0, 0), (4, 0), (0, 4), (4, 4), (2, 2)],
+    &[(0, 0), (6, 0), (0, 6), (6, 6), (3, 3), (3, 2)],
+    &[(-3, 5), (2, -4), (0, 0), (1, 1), (-1, 2), (4, 4), (-2, -2)],
+    &[(0, 3), (3, 0), (6, 3), (3, 6), (3, 3)],
+    &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)],
+    &[(3, 10), (0, 2), (1, 11), (7, 9), (7, 8)],
+    &[(7, 4), (5, 6), (0, 10), (9, 3)],
+];
+
+#[test]
+fn example() {
+    let points = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
+
+    assert_eq!(unbounded_points(&points, &Manhattan), vec![0, 1, 2, 5]);
+    for &engine in &[Engine::Naive, Engine::Flood] {
+        assert_eq!(
+            point_areas(&points, &Manhattan, engine),
+            vec![None, None, None, Some(9), Some(17), None]
+        );
+    }
+}
+
+#[test]
+fn negative_and_offset_coordinates() {
+    let example = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+
+    for &(dx, dy) in &[(-100, -50), (1000, 2000), (-7, 300)] {
+        let shifted: Vec<(i32, i32)> = example.iter().map(|&(x, y)| (x + dx, y + dy)).collect();
+        let points = points(&shifted);
+
+        assert_eq!(unbounded_points(&points, &Manhattan), vec![0, 1, 2, 5]);
+        assert_eq!(point_areas(&points, &Manhattan, Engine::Flood)[4], Some(17));
+    }
+}
+
+fn check_against_brute_force<M: Metric>(metric: &M, margin: i32) {
+    for coords in CASES {
+        let points = points(coords);
+        let (unbounded, areas) = brute_force(&points, metric, margin);
+
+        assert_eq!(unbounded_points(&points, metric), unbounded, "{:?}", coords);
+        for &engine in &[Engine::Naive, Engine::Flood] {
+            assert_eq!(point_areas(&points, metric, engine), areas, "{:?}", coords);
+        }
+    }
+}
+
+#[test]
+fn manhattan() {
+    check_against_brute_force(&Manhattan, 20);
+}
+
+#[test]
+fn weighted_manhattan() {
+    check_against_brute_force(&WeightedManhattan { wx: 1, wy: 1 }, 20);
+    check_against_brute_force(&WeightedManhattan { wx: 3, wy: 1 }, 20);
+    check_against_brute_force(&WeightedManhattan { wx: 2, wy: 5 }, 20);
+}
+
+#[test]
+fn chebyshev() {
+    check_against_brute_force(&Chebyshev, 20);
+}
+
+/* cells of nearly aligned points reach far from them */
+#[test]
+fn euclidean() {
+    check_against_brute_force(&Euclidean, 200);
+}
+
+#[test]
+fn metric_names() {
+    assert_eq!(
+        "chebyshev".parse::<MetricKind>().unwrap(),
+        MetricKind::Chebyshev
+    );
+    assert_eq!(
+        "weighted:3,1".parse::<MetricKind>().unwrap(),
+        MetricKind::WeightedManhattan(WeightedManhattan { wx: 3, wy: 1 })
+    );
+    assert!("weighted:0,1".parse::<MetricKind>().is_err());
+    assert!("taxicab".parse::<MetricKind>().is_err());
+}
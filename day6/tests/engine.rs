@@ -0,0 +1,68 @@
+use aoc18_rust_day6::{
+    point_areas, safe_area, Chebyshev, Engine, Manhattan, Point, WeightedManhattan,
+};
+use common::XorShift;
+
+/* random points of a square, some of them at negative coordinates */
+fn generate_points(nb_points: u32, size: u32) -> Vec<Point> {
+    XorShift::default()
+        .points(nb_points, size)
+        .iter()
+        .map(|&(x, y)| Point::new(x as i32 - 20, y as i32 - 20))
+        .collect()
+}
+
+#[test]
+fn engines_agree() {
+    for &(nb_points, size) in &[(1, 10), (10, 10), (30, 60), (100, 200)] {
+        let points = generate_points(nb_points, size);
+
+        assert_eq!(
+            point_areas(&points, &Manhattan, Engine::Naive),
+            point_areas(&points, &Manhattan, Engine::Flood),
+            "{} points",
+            nb_points
+        );
+        assert_eq!(
+            point_areas(&points, &Chebyshev, Engine::Naive),
+            point_areas(&points, &Chebyshev, Engine::Flood),
+            "{} points, chebyshev",
+            nb_points
+        );
+        /* up to safe regions past the bounding box */
+        for &threshold in &[0, 1, 50, nb_points * size / 4, nb_points * size / 2] {
+            assert_eq!(
+                safe_area(&points, threshold, &Manhattan, Engine::Naive),
+                safe_area(&points, threshold, &Manhattan, Engine::Flood),
+                "{} points, threshold {}",
+                nb_points,
+                threshold
+            );
+            let weighted = WeightedManhattan { wx: 2, wy: 3 };
+            assert_eq!(
+                safe_area(&points, threshold, &weighted, Engine::Naive),
+                safe_area(&points, threshold, &weighted, Engine::Flood),
+                "{} points, threshold {}, weighted",
+                nb_points,
+                threshold
+            );
+        }
+    }
+}
+
+#[test]
+fn duplicate_points() {
+    let points = vec![
+        Point::new(0, 0),
+        Point::new(3, 3),
+        Point::new(3, 3),
+        Point::new(6, 0),
+        Point::new(0, 6),
+        Point::new(6, 6),
+    ];
+
+    for &engine in &[Engine::Naive, Engine::Flood] {
+        assert_eq!(point_areas(&points, &Manhattan, engine)[1], Some(0));
+        assert_eq!(point_areas(&points, &Manhattan, engine)[2], Some(0));
+    }
+}
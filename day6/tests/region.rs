@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use aoc18_rust_day6::{
+    safe_area, safe_region, Chebyshev, Engine, Euclidean, Manhattan, Metric, Point, SafeRegion,
+    WeightedManhattan,
+};
+
+fn points(coords: &[(i32, i32)]) -> Vec<Point> {
+    coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+}
+
+/* Safe region found by brute force, on a grid large enough for thresholds up to 100 */
+fn brute_force<M: Metric>(points: &[Point], threshold: u32, metric: &M) -> SafeRegion {
+    let range = -110..=110;
+    let mut safe = HashSet::new();
+    for x in range.clone() {
+        for y in range.clone() {
+            let total: f64 = points
+                .iter()
+                .map(|p| metric.distance(p.x().abs_diff(x), p.y().abs_diff(y)))
+                .sum();
+            if total < f64::from(threshold) {
+                safe.insert((x, y));
+            }
+        }
+    }
+    let neighbours = |(x, y): (i32, i32)| [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)];
+
+    let perimeter = safe
+        .iter()
+        .flat_map(|&loc| neighbours(loc))
+        .filter(|n| !safe.contains(n))
+        .count() as u64;
+
+    let mut reached = HashSet::new();
+    let mut todo: Vec<(i32, i32)> = safe.iter().take(1).copied().collect();
+    while let Some(loc) = todo.pop() {
+        if reached.insert(loc) {
+            todo.extend(neighbours(loc).iter().filter(|n| safe.contains(n)));
+        }
+    }
+
+    let bounds = if safe.is_empty() {
+        None
+    } else {
+        let xs = safe.iter().map(|&(x, _)| i64::from(x));
+        let ys = safe.iter().map(|&(_, y)| i64::from(y));
+        Some((
+            (xs.clone().min().unwrap(), ys.clone().min().unwrap()),
+            (xs.max().unwrap(), ys.max().unwrap()),
+        ))
+    };
+    SafeRegion {
+        area: safe.len() as u64,
+        bounds,
+        perimeter,
+        connected: reached.len() == safe.len(),
+    }
+}
+
+#[test]
+fn example() {
+    let points = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
+
+    assert_eq!(
+        safe_region(&points, 32, &Manhattan).to_string(),
+        "area: 16\nbounding box: 2, 3 to 6, 6\nperimeter: 18\nconnected: yes"
+    );
+}
+
+#[test]
+fn past_the_bounding_box() {
+    /* a single point: a diamond of radius 9 around it */
+    let point = points(&[(-3, 7)]);
+    let region = safe_region(&point, 10, &Manhattan);
+
+    assert_eq!(region.area, 181);
+    assert_eq!(region.bounds, Some(((-12, -2), (6, 16))));
+    assert_eq!(region.perimeter, 4 * 19);
+    for &engine in &[Engine::Naive, Engine::Flood] {
+        assert_eq!(safe_area(&point, 10, &Manhattan, engine), 181);
+    }
+}
+
+fn check_against_brute_force<M: Metric>(metric: &M) {
+    let cases: &[&[(i32, i32)]] = &[
+        &[(0, 0)],
+        &[(0, 0), (0, 0)],
+        &[(0, 0), (10, 10)],
+        &[(0, 0), (3, 0), (0, 3), (3, 3)],
+        &[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)],
+        &[(-5, 2), (4, -3), (0, 0), (2, 7)],
+    ];
+
+    for coords in cases {
+        let points = points(coords);
+        for &threshold in &[0, 1, 2, 10, 21, 22, 30, 60, 90] {
+            let region = brute_force(&points, threshold, metric);
+
+            assert_eq!(
+                safe_region(&points, threshold, metric),
+                region,
+                "{:?}, threshold {}",
+                coords,
+                threshold
+            );
+            for &engine in &[Engine::Naive, Engine::Flood] {
+                assert_eq!(safe_area(&points, threshold, metric, engine), region.area);
+            }
+        }
+    }
+}
+
+#[test]
+fn manhattan() {
+    check_against_brute_force(&Manhattan);
+}
+
+#[test]
+fn weighted_manhattan() {
+    check_against_brute_force(&WeightedManhattan { wx: 3, wy: 1 });
+    check_against_brute_force(&WeightedManhattan { wx: 2, wy: 5 });
+}
+
+#[test]
+fn chebyshev() {
+    check_against_brute_force(&Chebyshev);
+}
+
+#[test]
+fn euclidean() {
+    check_against_brute_force(&Euclidean);
+}
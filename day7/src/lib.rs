@@ -0,0 +1,304 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::iter::Iterator;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use scan_fmt::scan_fmt;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+pub struct Day7 {
+    /* number of workers assembling the steps */
+    pub workers: u32,
+    /* duration of every step, on top of its letter cost */
+    pub base_cost: u32,
+}
+
+impl Default for Day7 {
+    fn default() -> Self {
+        Day7 {
+            workers: 5,
+            base_cost: 60,
+        }
+    }
+}
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Graph;
+    type Answer1 = String;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Graph> {
+        let deps: Vec<Dep> = parse_lines(input)?;
+
+        Ok(Graph::new(&deps))
+    }
+
+    fn part1(&self, graph: &Graph) -> Result<String> {
+        Ok(steps_order(graph))
+    }
+
+    fn part2(&self, graph: &Graph) -> Result<u32> {
+        Ok(assembly_time(graph, self.workers, self.base_cost))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "workers" => {
+                let workers = value.parse()?;
+                /* the steps need at least one worker */
+                if workers == 0 {
+                    return Err("invalid number of workers 0, expected at least one".into());
+                }
+                self.workers = workers;
+            }
+            "base-cost" => self.base_cost = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 7", name).into()),
+        }
+        Ok(())
+    }
+}
+
+fn steps_order(graph: &Graph) -> String {
+    let mut res = String::new();
+    let mut walker = GraphWalker::new(graph);
+
+    while let Some(node) = walker.queue.pop() {
+        res.push(node.name);
+        walker.add_next_nodes(node);
+    }
+
+    res
+}
+
+fn assembly_time(graph: &Graph, nb_workers: u32, base_cost: u32) -> u32 {
+    let mut workers = WorkersPool::new(nb_workers);
+    let mut processing_nodes: BinaryHeap<ProcessingEnd> = BinaryHeap::new();
+    let mut walker = GraphWalker::new(graph);
+    let mut current_time = 0;
+
+    while !walker.queue.is_empty() || !processing_nodes.is_empty() {
+        /* advance in time to the next available worker */
+        let worker = workers.get_next_available_worker();
+        current_time = std::cmp::max(worker.available_at, current_time);
+
+        /* add next nodes for all nodes processed */
+        /* FIXME: there is probably a better way to pop conditionally */
+        while !processing_nodes.is_empty() {
+            if processing_nodes.peek().unwrap().finished_on <= current_time {
+                let p = processing_nodes.pop().unwrap();
+                walker.add_next_nodes(p.node);
+            } else {
+                break;
+            }
+        }
+
+        /* consume node available at that time */
+        match walker.queue.pop() {
+            Some(node) => {
+                let finished_on = current_time + node.processing_cost() + base_cost;
+                worker.available_at = finished_on;
+
+                processing_nodes.push(ProcessingEnd { node, finished_on });
+            },
+            None => {
+                /* no available node, advance until next processed node */
+                if let Some(p) = processing_nodes.peek() {
+                    current_time = p.finished_on;
+                }
+            }
+        }
+    }
+
+    /* the final time is when the last worker is done */
+    workers.get_final_time()
+}
+
+/* {{{ WorkersPool */
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
+struct Worker {
+    /* indicate the next time the worker is available */
+    available_at: u32,
+}
+
+struct WorkersPool {
+    workers: Vec<Worker>,
+}
+
+impl WorkersPool {
+    fn new(nb_workers: u32) -> Self {
+        WorkersPool {
+            workers: vec![Worker { available_at: 0 }; nb_workers as usize],
+        }
+    }
+
+    fn get_next_available_worker(&mut self) -> &mut Worker {
+        self.workers.iter_mut().min().unwrap()
+    }
+
+    fn get_final_time(&self) -> u32 {
+        self.workers.iter().max().unwrap().available_at
+    }
+}
+
+/* }}} */
+/* {{{ ProcessingEnd */
+
+#[derive(Eq, PartialEq, Debug)]
+struct ProcessingEnd<'a> {
+    node: &'a Node,
+    finished_on: u32,
+}
+
+/* sort by name increasingly, for the priority queue */
+impl<'a> Ord for ProcessingEnd<'a> {
+    fn cmp(&self, other: &ProcessingEnd<'a>) -> std::cmp::Ordering {
+        other.finished_on.cmp(&self.finished_on)
+    }
+}
+
+impl<'a> PartialOrd for ProcessingEnd<'a> {
+    fn partial_cmp(&self, other: &ProcessingEnd<'a>) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/* }}} */
+/* {{{ Node */
+
+#[derive(Eq, PartialEq, Debug)]
+struct Node {
+    name: char,
+    next_nodes: Vec<char>,
+    dep_nodes: Vec<char>,
+}
+
+/* sort by name increasingly, for the priority queue */
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> std::cmp::Ordering {
+        other.name.cmp(&self.name)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Node {
+    fn processing_cost(&self) -> u32 {
+        (self.name as u32) - ('A' as u32) + 1
+    }
+}
+
+/* }}} */
+/* {{{ Graph */
+
+#[derive(Debug)]
+pub struct Graph {
+    map: HashMap<char, Node>,
+}
+
+impl Graph {
+    fn get_or_add_node(&mut self, name: char) -> &mut Node {
+        self.map.entry(name).or_insert(Node {
+            name,
+            next_nodes: Vec::new(),
+            dep_nodes: Vec::new(),
+        })
+    }
+
+    fn new(deps: &[Dep]) -> Self {
+        let mut graph = Graph {
+            map: HashMap::new(),
+        };
+
+        for dep in deps {
+            let node = graph.get_or_add_node(dep.dep);
+            node.next_nodes.push(dep.step);
+
+            let node = graph.get_or_add_node(dep.step);
+            node.dep_nodes.push(dep.dep);
+        }
+        graph
+    }
+}
+
+struct GraphWalker<'a> {
+    graph: &'a Graph,
+    dep_map: HashMap<char, usize>,
+    queue: BinaryHeap<&'a Node>,
+}
+
+impl<'a> GraphWalker<'a> {
+    fn new(graph: &'a Graph) -> Self {
+        /* map of node => number of dependencies */
+        let mut dep_map = HashMap::new();
+        /* priority queue for BFS */
+        let mut queue: BinaryHeap<&Node> = BinaryHeap::new();
+
+        for node in graph.map.values() {
+            if node.dep_nodes.is_empty() {
+                /* add sources in queue */
+                queue.push(node);
+            } else {
+                dep_map.insert(node.name, node.dep_nodes.len());
+            }
+        }
+
+        GraphWalker {
+            graph,
+            dep_map,
+            queue,
+        }
+    }
+
+    fn add_next_nodes(&mut self, node: &Node) {
+        /* push all new next nodes in queue */
+        for next in node.next_nodes.iter() {
+            let next_node = self.graph.map.get(next).unwrap();
+            let nb_deps = self.dep_map.get_mut(&next_node.name).unwrap();
+
+            if *nb_deps == 1 {
+                self.queue.push(next_node);
+            } else {
+                *nb_deps -= 1;
+            }
+        }
+    }
+}
+
+/* }}} */
+/* {{{ Dep */
+
+struct Dep {
+    step: char,
+    dep: char,
+}
+
+const DEP_PATTERN: &str = "Step {} must be finished before step {} can begin.";
+
+impl FromStr for Dep {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (dep, step) = scan_fmt!(s, DEP_PATTERN, char, char);
+        /* steps are uppercase letters, their duration depends on the letter */
+        let (dep, step) = (
+            dep.filter(char::is_ascii_uppercase),
+            step.filter(char::is_ascii_uppercase),
+        );
+
+        Ok(Dep {
+            dep: field(dep, "dependency", s, DEP_PATTERN)?,
+            step: field(step, "step", s, DEP_PATTERN)?,
+        })
+    }
+}
+
+/* }}} */
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::iter::Iterator;
+
+use common::Solution;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Default)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Node;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Node> {
+        let line = input.lines().next().ok_or("empty input")?;
+        let numbers = line
+            .split(' ')
+            .map(|s| s.parse::<u32>())
+            .collect::<std::result::Result<Vec<u32>, _>>()?;
+
+        Node::new(&mut numbers.into_iter())
+    }
+
+    fn part1(&self, root: &Node) -> Result<u32> {
+        Ok(metadata_sum(root))
+    }
+
+    fn part2(&self, root: &Node) -> Result<u32> {
+        Ok(root.value())
+    }
+}
+
+fn metadata_sum(node: &Node) -> u32 {
+    let children_sum: u32 = node.children.iter().map(metadata_sum).sum();
+    let node_sum: u32 = node.metadatas.iter().sum();
+
+    children_sum + node_sum
+}
+
+/* {{{ Node */
+
+fn next_number<I>(input: &mut I) -> Result<u32>
+    where I: Iterator<Item = u32>
+{
+    Ok(input.next().ok_or("truncated input, expected more numbers")?)
+}
+
+pub struct Node {
+    children: Vec<Node>,
+    metadatas: Vec<u32>,
+}
+
+impl Node {
+    fn new<I>(input: &mut I) -> Result<Self>
+        where I: Iterator<Item = u32>
+    {
+        let nb_children = next_number(input)?;
+        let nb_meta = next_number(input)?;
+
+        let mut children = Vec::new();
+        for _ in 0..nb_children {
+            children.push(Node::new(input)?);
+        }
+
+        let mut metadatas = Vec::new();
+        for _ in 0..nb_meta {
+            metadatas.push(next_number(input)?);
+        }
+
+        Ok(Node {
+            children,
+            metadatas,
+        })
+    }
+
+    fn value(&self) -> u32 {
+        if self.children.is_empty() {
+            self.metadatas.iter().sum()
+        } else {
+            let mut cache = HashMap::new();
+            let mut sum = 0;
+
+            for meta in self.metadatas.iter() {
+                /* metadata 0 refers to no child */
+                let index = match meta.checked_sub(1) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                if let Some(child) = self.children.get(index as usize) {
+                    let value = match cache.get(&index) {
+                        Some(value) => *value,
+                        None => {
+                            let value = child.value();
+                            cache.insert(index, value);
+                            value
+                        }
+                    };
+                    sum += value;
+                }
+            };
+            sum
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/* Compare the circle implementations on the part2 game of the committed input.
+ *
+ * Run with `cargo bench -p aoc18-rust-day9`.
+ */
+use std::time::Instant;
+
+use aoc18_rust_day9::{play_game, CircleKind};
+
+const FINAL_MARBLE: usize = 71250 * 100;
+const NB_PLAYERS: usize = 452;
+const RUNS: u32 = 5;
+
+fn main() {
+    for circle in &[CircleKind::Arena, CircleKind::LinkedList] {
+        let start = Instant::now();
+        let mut high_score = 0;
+
+        for _ in 0..RUNS {
+            high_score = play_game(FINAL_MARBLE, NB_PLAYERS, *circle);
+        }
+        println!(
+            "{:?}: high score {}, {:?} per game",
+            circle,
+            high_score,
+            start.elapsed() / RUNS
+        );
+    }
+}
@@ -0,0 +1,111 @@
+/* Circle of marbles, with a current marble.
+ *
+ * Every marble value is unique, so the marbles are stored in an arena indexed by their value,
+ * each slot holding the values of the marbles next to it. Rotating, inserting and removing are
+ * all O(1), with no allocation once the arena is large enough.
+ */
+pub struct MarbleCircle {
+    /* clockwise neighbour of each marble */
+    next: Vec<usize>,
+    /* counter-clockwise neighbour of each marble */
+    prev: Vec<usize>,
+    current: usize,
+    /* marble from which the circle is iterated: marble 0 until it is removed */
+    head: usize,
+    len: usize,
+}
+
+impl MarbleCircle {
+    /* Create a circle holding only marble 0, with room for marbles up to capacity - 1. */
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut next = Vec::with_capacity(capacity);
+        let mut prev = Vec::with_capacity(capacity);
+        next.push(0);
+        prev.push(0);
+
+        MarbleCircle {
+            next,
+            prev,
+            current: 0,
+            head: 0,
+            len: 1,
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn rotate_clockwise(&mut self, n: usize) {
+        for _ in 0..n {
+            self.current = self.next[self.current];
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&mut self, n: usize) {
+        for _ in 0..n {
+            self.current = self.prev[self.current];
+        }
+    }
+
+    /* Insert a marble clockwise of the current one, and make it the current marble. */
+    pub fn insert(&mut self, marble: usize) {
+        if marble >= self.next.len() {
+            self.next.resize(marble + 1, 0);
+            self.prev.resize(marble + 1, 0);
+        }
+
+        let prev = self.current;
+        let next = self.next[prev];
+        self.next[marble] = next;
+        self.prev[marble] = prev;
+        self.next[prev] = marble;
+        self.prev[next] = marble;
+
+        self.current = marble;
+        self.len += 1;
+    }
+
+    /* Remove the current marble, and make its clockwise neighbour the current marble.
+     * The last marble of the circle cannot be removed.
+     */
+    pub fn remove(&mut self) -> Option<usize> {
+        if self.len <= 1 {
+            return None;
+        }
+
+        let marble = self.current;
+        let prev = self.prev[marble];
+        let next = self.next[marble];
+        self.next[prev] = next;
+        self.prev[next] = prev;
+
+        if marble == self.head {
+            self.head = next;
+        }
+        self.current = next;
+        self.len -= 1;
+        Some(marble)
+    }
+
+    /* Iterate clockwise over the marbles, starting from marble 0 (or the marble that followed
+     * it, once removed).
+     */
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut marble = self.head;
+
+        (0..self.len).map(move |_| {
+            let m = marble;
+            marble = self.next[marble];
+            m
+        })
+    }
+}
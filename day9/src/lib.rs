@@ -0,0 +1,240 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use common::{field, parse_lines, ParseError, Solution};
+use linked_list::{Cursor, LinkedList};
+use scan_fmt::scan_fmt;
+
+mod circle;
+mod replay;
+
+pub use circle::MarbleCircle;
+pub use replay::{replay_game, write_replays, Replay, Turn};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+pub struct Day9 {
+    /* the last marble of part2 is this many times larger */
+    pub multiplier: usize,
+    /* implementation of the circle used to play the games */
+    pub circle: CircleKind,
+    /* if set, the part1 games are replayed and exported to this file */
+    pub replay: Option<PathBuf>,
+    /* the circle is recorded on every turn of replayed games with at most this many marbles */
+    pub max_snapshot: usize,
+}
+
+impl Default for Day9 {
+    fn default() -> Self {
+        Day9 {
+            multiplier: 100,
+            circle: CircleKind::Arena,
+            replay: None,
+            max_snapshot: 50,
+        }
+    }
+}
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Game>;
+    type Answer1 = Scores;
+    type Answer2 = Scores;
+
+    /* Each line of the input describes a game to play */
+    fn parse(&self, input: &str) -> Result<Vec<Game>> {
+        let games: Vec<Game> = parse_lines(input)?;
+
+        if games.is_empty() {
+            return Err(Box::from("no games in the input"));
+        }
+        Ok(games)
+    }
+
+    fn part1(&self, games: &Vec<Game>) -> Result<Scores> {
+        if let Some(path) = &self.replay {
+            let replays: Vec<Replay> = games
+                .iter()
+                .map(|g| replay_game(g.final_marble, g.nb_players, self.max_snapshot))
+                .collect();
+
+            write_replays(path, &replays)?;
+            return Ok(Scores(replays.iter().map(Replay::high_score).collect()));
+        }
+        Ok(Scores(
+            games
+                .iter()
+                .map(|g| play_game(g.final_marble, g.nb_players, self.circle))
+                .collect(),
+        ))
+    }
+
+    fn part2(&self, games: &Vec<Game>) -> Result<Scores> {
+        Ok(Scores(
+            games
+                .iter()
+                .map(|g| play_game(g.final_marble * self.multiplier, g.nb_players, self.circle))
+                .collect(),
+        ))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "multiplier" => self.multiplier = value.parse()?,
+            "circle" => self.circle = value.parse()?,
+            "replay" => self.replay = Some(PathBuf::from(value)),
+            "max-snapshot" => self.max_snapshot = value.parse()?,
+            _ => return Err(format!("unknown parameter {} for day 9", name).into()),
+        }
+        Ok(())
+    }
+}
+
+/* High scores of every game, one per line */
+pub struct Scores(pub Vec<usize>);
+
+impl fmt::Display for Scores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scores: Vec<String> = self.0.iter().map(|s| s.to_string()).collect();
+        write!(f, "{}", scores.join("\n"))
+    }
+}
+
+/* {{{ Game */
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircleKind {
+    /* arena-backed circle, see MarbleCircle */
+    Arena,
+    /* cursor over the linked_list crate, kept for comparison */
+    LinkedList,
+}
+
+impl FromStr for CircleKind {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "arena" => Ok(CircleKind::Arena),
+            "linked-list" => Ok(CircleKind::LinkedList),
+            _ => Err(format!("unknown circle {}, expected arena or linked-list", s).into()),
+        }
+    }
+}
+
+/* Play a game, and return the high score. */
+pub fn play_game(final_marble: usize, nb_players: usize, circle: CircleKind) -> usize {
+    match circle {
+        CircleKind::Arena => play_game_arena(final_marble, nb_players),
+        CircleKind::LinkedList => play_game_linked_list(final_marble, nb_players),
+    }
+}
+
+fn play_game_arena(final_marble: usize, nb_players: usize) -> usize {
+    let mut circle = MarbleCircle::with_capacity(final_marble + 1);
+    let mut scores = vec![0; nb_players];
+    let mut current_player = 0;
+
+    for marble in 1..=final_marble {
+        if marble % 23 == 0 {
+            circle.rotate_counter_clockwise(7);
+            let removed_marble = circle.remove().unwrap();
+            scores[current_player] += marble + removed_marble;
+        } else {
+            circle.rotate_clockwise(1);
+            circle.insert(marble);
+        }
+        current_player = (current_player + 1) % nb_players;
+    }
+
+    *scores.iter().max().unwrap()
+}
+
+/* }}} */
+/* {{{ Linked list game */
+
+fn play_game_linked_list(final_marble: usize, nb_players: usize) -> usize {
+    let mut list = LinkedList::new();
+    list.push_front(0);
+    let mut cursor = list.cursor();
+
+    let mut next_marble = 1;
+    let mut current_player = 0;
+
+    let mut scores = vec![0; nb_players];
+
+    while next_marble <= final_marble {
+        if next_marble % 23 == 0 {
+            go_backward(&mut cursor, 7);
+            let removed_marble = cursor.remove().unwrap();
+            scores[current_player] += next_marble + removed_marble;
+        } else {
+            go_forward(&mut cursor, 2);
+            cursor.insert(next_marble);
+        }
+        next_marble += 1;
+        current_player = (current_player + 1) % nb_players;
+    }
+
+    *scores.iter().max().unwrap()
+}
+
+/* The linked_list crate provides a cursor over a linked list, however the cursor does not handle
+ * the looping seamlessly: when looping back to the "start" of the list, next/prev will return
+ * None, and need to be called again to return the right node.
+ * Those functions handle this case transparently.
+ */
+
+fn go_forward(cursor: &mut Cursor<usize>, n: usize) {
+    let mut i = n;
+
+    while i > 0 {
+        if cursor.next().is_some() {
+            i -= 1;
+        }
+    }
+}
+
+fn go_backward(cursor: &mut Cursor<usize>, n: usize) {
+    let mut i = n;
+
+    while i > 0 {
+        if cursor.prev().is_some() {
+            i -= 1;
+        }
+    }
+}
+
+/* }}} */
+/* {{{ Game input */
+
+pub struct Game {
+    nb_players: usize,
+    /* value of the last marble played */
+    final_marble: usize,
+}
+
+const GAME_PATTERN: &str = "{d} players; last marble is worth {d} points";
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, ParseError> {
+        let (nb_players, final_marble) = scan_fmt!(s, GAME_PATTERN, usize, usize);
+        let nb_players = field(nb_players, "players", s, GAME_PATTERN)?;
+
+        /* the game needs at least one player */
+        if nb_players == 0 {
+            return Err(ParseError::new(s, GAME_PATTERN).with_field("players"));
+        }
+        Ok(Game {
+            nb_players,
+            final_marble: field(final_marble, "last marble", s, GAME_PATTERN)?,
+        })
+    }
+}
+
+/* }}} */
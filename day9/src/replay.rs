@@ -0,0 +1,198 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::MarbleCircle;
+
+/* A turn of a game, in which a player places a marble, or keeps it and removes another one. */
+pub struct Turn {
+    /* player, starting at 1 as in the puzzle statement */
+    pub player: usize,
+    /* marble played during the turn */
+    pub marble: usize,
+    /* marble removed from the circle, if the played marble was kept */
+    pub removed: Option<usize>,
+    /* points scored during the turn */
+    pub score_delta: usize,
+    /* marbles of the circle after the turn, and the current one, for small games only */
+    pub circle: Option<(Vec<usize>, usize)>,
+}
+
+/* Every turn of a game. */
+pub struct Replay {
+    pub nb_players: usize,
+    pub turns: Vec<Turn>,
+}
+
+/* Play a game while recording every turn. The circle is recorded after each turn if the game
+ * has at most max_snapshot marbles.
+ */
+pub fn replay_game(final_marble: usize, nb_players: usize, max_snapshot: usize) -> Replay {
+    let mut circle = MarbleCircle::with_capacity(final_marble + 1);
+    let mut turns = Vec::with_capacity(final_marble);
+    let snapshot = final_marble <= max_snapshot;
+
+    for marble in 1..=final_marble {
+        let mut turn = Turn {
+            player: (marble - 1) % nb_players + 1,
+            marble,
+            removed: None,
+            score_delta: 0,
+            circle: None,
+        };
+
+        if marble % 23 == 0 {
+            circle.rotate_counter_clockwise(7);
+            let removed_marble = circle.remove().unwrap();
+            turn.removed = Some(removed_marble);
+            turn.score_delta = marble + removed_marble;
+        } else {
+            circle.rotate_clockwise(1);
+            circle.insert(marble);
+        }
+        if snapshot {
+            turn.circle = Some((circle.iter().collect(), circle.current()));
+        }
+        turns.push(turn);
+    }
+
+    Replay { nb_players, turns }
+}
+
+impl Replay {
+    pub fn high_score(&self) -> usize {
+        self.final_scores().into_iter().max().unwrap_or(0)
+    }
+
+    pub fn final_scores(&self) -> Vec<usize> {
+        let mut scores = vec![0; self.nb_players];
+
+        for turn in &self.turns {
+            scores[turn.player - 1] += turn.score_delta;
+        }
+        scores
+    }
+
+    /* Turns in which a player scored, with the total score of this player after the turn. */
+    fn scoring_turns(&self) -> impl Iterator<Item = (&Turn, usize)> {
+        let mut scores = vec![0; self.nb_players];
+
+        self.turns
+            .iter()
+            .filter(|turn| turn.score_delta > 0)
+            .map(move |turn| {
+                scores[turn.player - 1] += turn.score_delta;
+                (turn, scores[turn.player - 1])
+            })
+    }
+
+    /* Write every turn, and the circle after it if recorded, as in the puzzle statement. */
+    pub fn write_log<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        for turn in &self.turns {
+            match turn.removed {
+                Some(removed) => writeln!(
+                    out,
+                    "[{}] keeps {} and removes {}, scoring {}",
+                    turn.player, turn.marble, removed, turn.score_delta
+                )?,
+                None => writeln!(out, "[{}] places {}", turn.player, turn.marble)?,
+            }
+            if let Some((marbles, current)) = &turn.circle {
+                let marbles: Vec<String> = marbles
+                    .iter()
+                    .map(|m| {
+                        if m == current {
+                            format!("({})", m)
+                        } else {
+                            format!(" {} ", m)
+                        }
+                    })
+                    .collect();
+                writeln!(out, "{}", marbles.concat())?;
+            }
+        }
+        Ok(())
+    }
+
+    /* Write the score timeline as CSV, one row per scoring turn. The game column allows
+     * concatenating the timelines of several games.
+     */
+    pub fn write_csv<W: Write>(&self, out: &mut W, game: usize) -> io::Result<()> {
+        for (turn, score) in self.scoring_turns() {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                game, turn.marble, turn.player, turn.score_delta, score
+            )?;
+        }
+        Ok(())
+    }
+
+    /* Write the score timeline as JSON: for each player, the turns in which this player scored,
+     * with the total score after the turn.
+     */
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut timelines = vec![Vec::new(); self.nb_players];
+
+        for (turn, score) in self.scoring_turns() {
+            timelines[turn.player - 1].push(format!(
+                "{{\"turn\":{},\"delta\":{},\"score\":{}}}",
+                turn.marble, turn.score_delta, score
+            ));
+        }
+
+        write!(
+            out,
+            "{{\"players\":{},\"turns\":{},\"high_score\":{},\"timelines\":[",
+            self.nb_players,
+            self.turns.len(),
+            self.high_score()
+        )?;
+        for (idx, timeline) in timelines.iter().enumerate() {
+            if idx > 0 {
+                write!(out, ",")?;
+            }
+            write!(
+                out,
+                "{{\"player\":{},\"scores\":[{}]}}",
+                idx + 1,
+                timeline.join(",")
+            )?;
+        }
+        write!(out, "]}}")
+    }
+}
+
+/* Export the replays of several games to a file. The format depends on the extension: the score
+ * timelines for .csv and .json files, and the log of every turn otherwise.
+ */
+pub fn write_replays(path: &Path, replays: &[Replay]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => {
+            writeln!(out, "game,turn,player,delta,score")?;
+            for (idx, replay) in replays.iter().enumerate() {
+                replay.write_csv(&mut out, idx + 1)?;
+            }
+        }
+        Some("json") => {
+            write!(out, "[")?;
+            for (idx, replay) in replays.iter().enumerate() {
+                if idx > 0 {
+                    write!(out, ",")?;
+                }
+                replay.write_json(&mut out)?;
+            }
+            writeln!(out, "]")?;
+        }
+        _ => {
+            for (idx, replay) in replays.iter().enumerate() {
+                writeln!(out, "game {}:", idx + 1)?;
+                replay.write_log(&mut out)?;
+            }
+        }
+    }
+    out.flush()
+}
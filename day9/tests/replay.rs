@@ -0,0 +1,38 @@
+use aoc18_rust_day9::{play_game, replay_game, CircleKind};
+
+#[test]
+fn replay_matches_game() {
+    let replay = replay_game(1618, 10, 0);
+
+    assert_eq!(replay.turns.len(), 1618);
+    assert_eq!(replay.high_score(), 8317);
+    assert_eq!(replay.high_score(), play_game(1618, 10, CircleKind::Arena));
+    assert!(replay.turns.iter().all(|t| t.circle.is_none()));
+}
+
+#[test]
+fn replay_records_turns() {
+    let replay = replay_game(25, 9, 25);
+    let turn = &replay.turns[22];
+
+    assert_eq!(turn.player, 5);
+    assert_eq!(turn.marble, 23);
+    assert_eq!(turn.removed, Some(9));
+    assert_eq!(turn.score_delta, 32);
+
+    let (marbles, current) = turn.circle.as_ref().unwrap();
+    assert_eq!(*current, 19);
+    assert_eq!(
+        marbles,
+        &[0, 16, 8, 17, 4, 18, 19, 2, 20, 10, 21, 5, 22, 11, 1, 12, 6, 13, 3, 14, 7, 15]
+    );
+}
+
+#[test]
+fn replay_exports_timeline() {
+    let replay = replay_game(25, 9, 0);
+    let mut csv = Vec::new();
+    replay.write_csv(&mut csv, 1).unwrap();
+
+    assert_eq!(String::from_utf8(csv).unwrap(), "1,23,5,32,32\n");
+}